@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    dictionary_api::DEFAULT_DICTIONARY_URL, http::HttpClient,
+    random_word_api::DEFAULT_RANDOM_WORD_API_URL, Dictionary, DictionaryError, HttpConfig, Language,
+    RandomWordBackend, TranslationBackend, TransportError,
+};
+
+/// Configures where and how a `Dictionary` fetches its data,
+/// e.g. to use a mirror, a self hosted instance or a local test server
+pub struct DictionaryBuilder {
+    dictionary_url: String,
+    random_word_url: String,
+    random_word_backend: RandomWordBackend,
+    language: Language,
+    translations: Option<Arc<dyn TranslationBackend>>,
+    headers: HeaderMap,
+    client: Option<reqwest::Client>,
+    config: HttpConfig,
+}
+
+impl Default for DictionaryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DictionaryBuilder {
+    pub fn new() -> Self {
+        Self {
+            dictionary_url: DEFAULT_DICTIONARY_URL.to_owned(),
+            random_word_url: DEFAULT_RANDOM_WORD_API_URL.to_owned(),
+            random_word_backend: RandomWordBackend::default(),
+            language: Language::default(),
+            translations: None,
+            headers: HeaderMap::new(),
+            client: None,
+            config: HttpConfig::default(),
+        }
+    }
+
+    /// The base url of a dictionaryapi.dev compatible api, e.g. `https://api.dictionaryapi.dev/api/v2`
+    pub fn dictionary_url(mut self, url: impl Into<String>) -> Self {
+        self.dictionary_url = url.into();
+        self
+    }
+
+    /// The base url of a random-word-api compatible api, e.g. `https://random-word-api.vercel.app`
+    pub fn random_word_url(mut self, url: impl Into<String>) -> Self {
+        self.random_word_url = url.into();
+        self
+    }
+
+    /// Where random words come from, the random word url is only used by `RandomWordBackend::RandomWordApi`
+    pub fn random_word_backend(mut self, backend: RandomWordBackend) -> Self {
+        self.random_word_backend = backend;
+        self
+    }
+
+    /// The language words are looked up in, english by default.
+    /// Building fails when one of the backends doesn't support it.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Attaches translations to the looked up words.
+    /// Building fails when the backend doesn't translate from the language of the dictionary.
+    pub fn translations(mut self, backend: Arc<dyn TranslationBackend>) -> Self {
+        self.translations = Some(backend);
+        self
+    }
+
+    /// Adds a header that is sent with every request to both apis
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Uses an existing client instead of building one.
+    /// The timeouts of the config are ignored in this case, they have to be set on the client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn config(mut self, config: HttpConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn build(self) -> Result<Dictionary, DictionaryError> {
+        if !Dictionary::languages_supported_by(&self.dictionary_url, self.random_word_backend).contains(&self.language) {
+            return Err(DictionaryError::UnsupportedLanguage(self.language));
+        }
+        if let Some(translations) = &self.translations {
+            if translations.source_language() != self.language {
+                return Err(DictionaryError::UnsupportedLanguage(self.language));
+            }
+        }
+        let client = match self.client {
+            Some(client) => client,
+            None => HttpClient::build_client(&self.config)
+                .map_err(|error| DictionaryError::Client(TransportError(error)))?,
+        };
+        Ok(Dictionary {
+            client: HttpClient::new(client, self.headers, self.config),
+            dictionary_url: self.dictionary_url,
+            random_word_url: self.random_word_url,
+            random_word_backend: self.random_word_backend,
+            language: self.language,
+            translations: self.translations,
+        })
+    }
+}
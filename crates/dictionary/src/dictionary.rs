@@ -1,13 +1,33 @@
+use crate::{
+    frequency::{frequency_rank, Level},
+    Language,
+};
+
 #[derive(Debug)]
 pub struct Word {
     pub word: String,
+    pub language: Language,
     pub phonetic: Option<String>,
     pub phonetics: Vec<Phonetic>,
     pub origin: Option<String>,
     pub meanings: Vec<WordMeaning>,
+    /// The translations into the native language of the learner, empty without a translation backend
+    pub translations: Vec<String>,
 }
 
 impl Word {
+    /// How common the word is, 1 being the most common word. Only known for english words
+    pub fn frequency_rank(&self) -> Option<u32> {
+        (self.language == Language::English)
+            .then(|| frequency_rank(&self.word))
+            .flatten()
+    }
+
+    /// The level of the word, None when it isn't known for the language of the word
+    pub fn level(&self) -> Option<Level> {
+        (self.language == Language::English).then(|| Level::of(&self.word))
+    }
+
     pub fn all_synonyms(&self) -> impl Iterator<Item = &str> {
         self.meanings
             .iter()
@@ -65,6 +85,21 @@ pub enum PartOfSpeech {
     Interjection,
 }
 
+impl PartOfSpeech {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Pronoun => "pronoun",
+            PartOfSpeech::Verb => "verb",
+            PartOfSpeech::Adjective => "adjective",
+            PartOfSpeech::Adverb => "adverb",
+            PartOfSpeech::Preposition => "preposition",
+            PartOfSpeech::Conjunction => "conjunction",
+            PartOfSpeech::Interjection => "interjection",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WordDefinition {
     pub definition: String,
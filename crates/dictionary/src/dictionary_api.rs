@@ -1,16 +1,32 @@
+use reqwest::{StatusCode, Url};
 use serde::Deserialize;
 
-use crate::{DictionaryError, NotFoundError, PartOfSpeech, Phonetic, Word, WordDefinition, WordMeaning};
+use crate::{
+    http::{read_json, unexpected_status, HttpClient},
+    DictionaryError, Language, NotFoundError, PartOfSpeech, Phonetic, Word, WordDefinition, WordMeaning,
+};
 
 // url: https://dictionaryapi.dev/
 
-const DICTIONARY_URL: &'static str = "https://api.dictionaryapi.dev/api/v2/entries/en/";
-
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum ApiResponse {
-    Found(Vec<ApiWord>),
-    Error(ApiError),
+pub(crate) const DEFAULT_DICTIONARY_URL: &str = "https://api.dictionaryapi.dev/api/v2";
+
+/// The languages dictionaryapi.dev was checked to have entries for, the language code is part of the url of an entry
+const DICTIONARY_API_LANGUAGES: &[Language] = &[Language::English];
+const DICTIONARY_API_HOST: &str = "dictionaryapi.dev";
+
+/// The languages the api at the url has entries for.
+/// Another api with the same interface, e.g. a self hosted one, is trusted to know every language
+pub(crate) fn dictionary_api_languages(url: &str) -> &'static [Language] {
+    let dictionaryapi_dev = Url::parse(url)
+        .is_ok_and(|url| {
+            url.host_str()
+                .is_some_and(|host| host == DICTIONARY_API_HOST || host.ends_with(".dictionaryapi.dev"))
+        });
+    if dictionaryapi_dev {
+        DICTIONARY_API_LANGUAGES
+    } else {
+        &Language::ALL
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,10 +60,12 @@ impl TryFrom<ApiWord> for Word {
         } else {
             Ok(Self {
                 word: word.word,
+                language: Language::default(),
                 phonetic: word.phonetic,
                 phonetics: word.phonetics.into_iter().map(Phonetic::from).collect(),
                 origin: word.origin,
                 meanings: meanings.into_iter().map(Result::unwrap).collect(),
+                translations: Vec::new(),
             })
         }
     }
@@ -143,24 +161,47 @@ impl From<ApiWordDefinition> for WordDefinition {
 }
 
 
-pub(crate) async fn get_definition(client: &reqwest::Client, word: &str) -> Result<Word, DictionaryError> {
-    let url = format!("{DICTIONARY_URL}{word}");
-    let res: reqwest::Response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(DictionaryError::Fetch)?;
-    res.json::<ApiResponse>()
-        .await
-        .map_err(DictionaryError::Deserialize)
-        .and_then(|res| match res {
-            ApiResponse::Found(mut words) => words
+pub(crate) async fn get_definition(
+    client: &HttpClient,
+    base_url: &str,
+    word: &str,
+    language: Language,
+) -> Result<Word, DictionaryError> {
+    let url = entry_url(base_url, word, language);
+    let res: reqwest::Response = client.get(&url, &[]).await?;
+    match res.status() {
+        StatusCode::NOT_FOUND => {
+            let error: ApiError = read_json(res).await?;
+            Err(DictionaryError::NotFound(NotFoundError {
+                message: error.message,
+            }))
+        }
+        status if status.is_success() => {
+            let mut words: Vec<ApiWord> = read_json(res).await?;
+            let word: Word = words
                 .pop()
-                .unwrap()
+                .ok_or(DictionaryError::EmptyResponse)?
                 .try_into()
-                .map_err(DictionaryError::Conversion),
-            ApiResponse::Error(error) => Err(DictionaryError::NotFound(NotFoundError {
-                message: error.message,
-            })),
-        })
-}
\ No newline at end of file
+                .map_err(|error: UnknownPartOfSpeech| DictionaryError::UnknownPartOfSpeech {
+                    kind: error.kind,
+                })?;
+            Ok(Word { language, ..word })
+        }
+        _ => Err(unexpected_status(res).await),
+    }
+}
+
+/// The url of the entry of a word, the word is percent encoded so phrases and punctuation stay a single path segment
+fn entry_url(base_url: &str, word: &str, language: Language) -> String {
+    let entries = format!("{}/entries/{}", base_url.trim_end_matches('/'), language.code());
+    match Url::parse(&entries) {
+        Ok(mut url) => {
+            if let Ok(mut segments) = url.path_segments_mut() {
+                segments.push(word);
+            }
+            url.into()
+        }
+        // reqwest reports the invalid url when it is requested
+        Err(_) => format!("{entries}/{word}"),
+    }
+}
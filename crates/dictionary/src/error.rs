@@ -0,0 +1,153 @@
+use std::{error::Error, fmt::Display, time::Duration};
+
+use crate::Language;
+
+#[derive(Debug)]
+pub enum DictionaryError {
+    /// The http client couldn't be created
+    Client(TransportError),
+    /// The api didn't answer within the configured timeout, even after retrying
+    Timeout(TransportError),
+    /// The api couldn't be reached, even after retrying
+    Connection(TransportError),
+    /// The api kept asking to slow down until no retries were left, or asked to wait longer than the longest backoff
+    RateLimited { retry_after: Option<Duration> },
+    /// The api answered with a status it isn't expected to answer with
+    Status { status: u16, body: String },
+    /// The api answered with data that doesn't have the expected shape
+    MalformedPayload(MalformedPayload),
+    /// The api answered successfully but without any entries
+    EmptyResponse,
+    UnknownPartOfSpeech { kind: String },
+    NotFound(NotFoundError),
+    /// Not enough distinct random words matching the query were found, even after fetching more
+    NotEnoughWords { requested: usize, found: usize },
+    /// One of the configured backends has no data for the language
+    UnsupportedLanguage(Language),
+}
+
+impl DictionaryError {
+    pub(crate) fn from_transport(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            DictionaryError::Timeout(TransportError(error))
+        } else {
+            DictionaryError::Connection(TransportError(error))
+        }
+    }
+}
+
+impl Error for DictionaryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DictionaryError::Client(error)
+            | DictionaryError::Timeout(error)
+            | DictionaryError::Connection(error) => Some(error),
+            DictionaryError::MalformedPayload(error) => Some(error),
+            DictionaryError::NotFound(error) => Some(error),
+            DictionaryError::RateLimited { .. }
+            | DictionaryError::Status { .. }
+            | DictionaryError::EmptyResponse
+            | DictionaryError::UnknownPartOfSpeech { .. }
+            | DictionaryError::NotEnoughWords { .. }
+            | DictionaryError::UnsupportedLanguage(_) => None,
+        }
+    }
+}
+
+impl Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictionaryError::Client(_) => f.write_str("Failed to create the http client"),
+            DictionaryError::Timeout(_) => f.write_str("The dictionary took too long to answer"),
+            DictionaryError::Connection(_) => f.write_str("Failed to reach the dictionary"),
+            DictionaryError::RateLimited {
+                retry_after: Some(retry_after),
+            } => f.write_fmt(format_args!(
+                "The dictionary is rate limiting requests, try again in {} seconds",
+                retry_after.as_secs()
+            )),
+            DictionaryError::RateLimited { retry_after: None } => {
+                f.write_str("The dictionary is rate limiting requests")
+            }
+            DictionaryError::Status { status, body } => {
+                f.write_fmt(format_args!("The dictionary answered with status {status}"))?;
+                if !body.is_empty() {
+                    f.write_fmt(format_args!(": {body}"))?;
+                }
+                Ok(())
+            }
+            DictionaryError::MalformedPayload(error) => f.write_fmt(format_args!(
+                "The dictionary answered with unexpected data at '{}'",
+                error.path
+            )),
+            DictionaryError::EmptyResponse => f.write_str("The dictionary answered without any entries"),
+            DictionaryError::UnknownPartOfSpeech { kind } => {
+                f.write_fmt(format_args!("Couldn't process part of speech '{kind}'"))
+            }
+            DictionaryError::NotFound(_) => f.write_str("The word wasn't found"),
+            DictionaryError::NotEnoughWords { requested, found } => f.write_fmt(format_args!(
+                "Only found {found} of {requested} random words"
+            )),
+            DictionaryError::UnsupportedLanguage(language) => {
+                f.write_fmt(format_args!("{language} isn't supported by the dictionary"))
+            }
+        }
+    }
+}
+
+/// A failure of the underlying http transport
+#[derive(Debug)]
+pub struct TransportError(pub(crate) reqwest::Error);
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub struct MalformedPayload {
+    /// Where in the json the problem is, e.g. `[0].meanings[1].definitions`
+    pub path: String,
+    source: serde_json::Error,
+}
+
+impl MalformedPayload {
+    pub(crate) fn new(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Self {
+            path: error.path().to_string(),
+            source: error.into_inner(),
+        }
+    }
+}
+
+impl Error for MalformedPayload {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl Display for MalformedPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} at '{}'", self.source, self.path))
+    }
+}
+
+#[derive(Debug)]
+pub struct NotFoundError {
+    pub message: String,
+}
+
+impl Error for NotFoundError {}
+
+impl Display for NotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
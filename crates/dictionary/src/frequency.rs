@@ -0,0 +1,134 @@
+use std::{collections::HashMap, fmt::Display, ops::RangeInclusive, sync::OnceLock};
+
+/// Common english words, the most frequent ones ranked and less common ones grouped by level
+const FREQUENCY_LIST: &str = include_str!("../data/frequency.txt");
+
+/// A CEFR like level of how advanced a word is, based on how common it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    A1,
+    A2,
+    B1,
+    B2,
+    C1,
+    /// Words that aren't in the frequency list
+    C2,
+}
+
+impl Level {
+    pub const ALL: [Level; 6] = [Level::A1, Level::A2, Level::B1, Level::B2, Level::C1, Level::C2];
+
+    /// The highest rank of every level for the ranked words. The ranked part of the list only covers the
+    /// first levels, words ranked below the last limit are B1 and the rarer levels come from the level sections.
+    const RANK_LIMITS: [(Level, u32); 2] = [(Level::A1, 250), (Level::A2, 800)];
+
+    pub fn from_rank(rank: Option<u32>) -> Self {
+        match rank {
+            Some(rank) => Self::RANK_LIMITS
+                .iter()
+                .find(|(_, limit)| rank <= *limit)
+                .map_or(Level::B1, |(level, _)| *level),
+            None => Level::C2,
+        }
+    }
+
+    /// The level of a word, words that aren't listed are C2
+    pub fn of(word: &str) -> Self {
+        lookup(word).map_or(Level::C2, |listed| listed.level)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::A1 => "A1",
+            Level::A2 => "A2",
+            Level::B1 => "B1",
+            Level::B2 => "B2",
+            Level::C1 => "C1",
+            Level::C2 => "C2",
+        }
+    }
+
+    pub fn parse(level: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|known| known.as_str().eq_ignore_ascii_case(level.trim()))
+    }
+
+    /// The levels next to this one and itself
+    pub fn neighborhood(&self) -> RangeInclusive<Level> {
+        let index = *self as usize;
+        Self::ALL[index.saturating_sub(1)]..=Self::ALL[(index + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A listed word with its rank, only the words of the frequency ordered part have one
+struct ListedWord {
+    word: &'static str,
+    rank: Option<u32>,
+    level: Level,
+}
+
+fn listed_words() -> &'static [ListedWord] {
+    static WORDS: OnceLock<Vec<ListedWord>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        let mut words = Vec::new();
+        // None while in the frequency ordered part, then the level of the current section
+        let mut section = None;
+        for line in FREQUENCY_LIST
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            if let Some(level) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                section = Some(Level::parse(level).unwrap_or_else(|| panic!("Unknown level section '{line}'")));
+                continue;
+            }
+            let rank = match section {
+                Some(_) => None,
+                None => Some(words.len() as u32 + 1),
+            };
+            words.push(ListedWord {
+                word: line,
+                rank,
+                level: section.unwrap_or_else(|| Level::from_rank(rank)),
+            });
+        }
+        words
+    })
+}
+
+fn index() -> &'static HashMap<&'static str, &'static ListedWord> {
+    static INDEX: OnceLock<HashMap<&'static str, &'static ListedWord>> = OnceLock::new();
+    INDEX.get_or_init(|| listed_words().iter().map(|listed| (listed.word, listed)).collect())
+}
+
+fn lookup(word: &str) -> Option<&'static ListedWord> {
+    index().get(&word.trim().to_lowercase()[..]).copied()
+}
+
+/// Whether the word is in the frequency list, ranked or not
+pub(crate) fn is_listed(word: &str) -> bool {
+    lookup(word).is_some()
+}
+
+/// How common a word is, 1 being the most common word.
+/// None when the word isn't in the frequency ordered part of the list.
+pub fn frequency_rank(word: &str) -> Option<u32> {
+    lookup(word).and_then(|listed| listed.rank)
+}
+
+/// Every listed word within the given levels, the ranked ones first and most frequent first.
+/// C2 words aren't listed so they are never returned.
+pub fn words_in_levels(levels: RangeInclusive<Level>) -> Vec<&'static str> {
+    listed_words()
+        .iter()
+        .filter(|listed| levels.contains(&listed.level))
+        .map(|listed| listed.word)
+        .collect()
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Response, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use tokio::{
+    sync::Mutex,
+    time::{sleep, sleep_until, Instant},
+};
+
+use crate::{DictionaryError, MalformedPayload};
+
+/// Controls how the dictionary talks to the apis behind it
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// The time limit of a whole request, from connecting until the body was read
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// How many times a request is retried after a connection error, a timeout or a 5xx/429 response
+    pub max_retries: u32,
+    /// The delay before the first retry, every following retry waits twice as long
+    pub initial_backoff: Duration,
+    /// The longest delay before a retry, a server asking to wait longer fails the request with `RateLimited`
+    pub max_backoff: Duration,
+    /// The minimum time between two requests, shared by all the apis
+    pub min_request_interval: Duration,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(8),
+            min_request_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+pub(crate) struct HttpClient {
+    client: reqwest::Client,
+    /// Sent with every request, on top of the client's default headers
+    headers: HeaderMap,
+    config: HttpConfig,
+    /// The earliest moment the next request may be sent
+    next_request: Mutex<Instant>,
+}
+
+impl HttpClient {
+    /// Builds a client out of the config's timeouts
+    pub(crate) fn build_client(config: &HttpConfig) -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .build()
+    }
+
+    pub(crate) fn new(client: reqwest::Client, headers: HeaderMap, config: HttpConfig) -> Self {
+        Self {
+            client,
+            headers,
+            config,
+            next_request: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Sends a get request, retrying transient failures.
+    /// Responses with other error statuses are returned as is, the apis describe errors in their body.
+    pub(crate) async fn get(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<Response, DictionaryError> {
+        let mut attempt = 0;
+        loop {
+            self.wait_for_turn().await;
+            let result = self
+                .client
+                .get(url)
+                .headers(self.headers.clone())
+                .query(query)
+                .send()
+                .await;
+            let retry_delay = match &result {
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    let delay = retry_after(response);
+                    // waiting longer than the longest backoff would stall every other request as well
+                    let too_long = delay.is_some_and(|delay| delay > self.config.max_backoff);
+                    if attempt >= self.config.max_retries || too_long {
+                        return Err(DictionaryError::RateLimited { retry_after: delay });
+                    }
+                    let delay = delay.unwrap_or_else(|| self.backoff(attempt));
+                    // every other request would be rejected as well, so they all wait
+                    self.delay_requests(delay).await;
+                    Some(delay)
+                }
+                Ok(response) if response.status().is_server_error() => Some(self.backoff(attempt)),
+                Ok(_) => None,
+                Err(error) if error.is_connect() || error.is_timeout() => Some(self.backoff(attempt)),
+                Err(_) => None,
+            };
+            match retry_delay {
+                Some(delay) if attempt < self.config.max_retries => {
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => {
+                    let response = result.map_err(DictionaryError::from_transport)?;
+                    if response.status().is_server_error() {
+                        return Err(unexpected_status(response).await);
+                    }
+                    return Ok(response);
+                }
+            }
+        }
+    }
+
+    async fn wait_for_turn(&self) {
+        let turn = {
+            let mut next_request = self.next_request.lock().await;
+            let turn = (*next_request).max(Instant::now());
+            *next_request = turn + self.config.min_request_interval;
+            turn
+        };
+        sleep_until(turn).await;
+    }
+
+    async fn delay_requests(&self, delay: Duration) {
+        let mut next_request = self.next_request.lock().await;
+        *next_request = (*next_request).max(Instant::now() + delay);
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.config
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.config.max_backoff)
+    }
+}
+
+/// Reads a json body, pointing at the exact spot when it doesn't have the expected shape
+pub(crate) async fn read_json<T: DeserializeOwned>(response: Response) -> Result<T, DictionaryError> {
+    let body = response
+        .text()
+        .await
+        .map_err(DictionaryError::from_transport)?;
+    let mut deserializer = serde_json::Deserializer::from_str(&body);
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|error| DictionaryError::MalformedPayload(MalformedPayload::new(error)))
+}
+
+pub(crate) async fn unexpected_status(response: Response) -> DictionaryError {
+    let status = response.status().as_u16();
+    // the body is only there to help explaining the error, so failing to read it is fine
+    let body = response.text().await.unwrap_or_default();
+    DictionaryError::Status { status, body }
+}
+
+/// Reads the delay a server asked for, only the delay-seconds form of the header is supported
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
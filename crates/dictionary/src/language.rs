@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+/// A language words can be looked up in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+    Italian,
+    Portuguese,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+        Language::Italian,
+        Language::Portuguese,
+    ];
+
+    /// The ISO 639-1 code of the language, e.g. `en`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Italian => "Italian",
+            Language::Portuguese => "Portuguese",
+        }
+    }
+
+    /// Parses a language from its code or its english name, ignoring case
+    pub fn parse(language: &str) -> Option<Self> {
+        let language = language.trim();
+        Self::ALL.into_iter().find(|known| {
+            known.code().eq_ignore_ascii_case(language) || known.name().eq_ignore_ascii_case(language)
+        })
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
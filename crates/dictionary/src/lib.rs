@@ -1,75 +1,265 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::HashSet, pin::pin, sync::Arc};
 
-use dictionary_api::{get_definition, UnknownPartOfSpeech};
+use dictionary_api::{dictionary_api_languages, get_definition};
+use futures::{stream, StreamExt};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
 
+mod builder;
 mod dictionary;
 mod dictionary_api;
+mod error;
+mod frequency;
+mod http;
+mod language;
+mod morphology;
+mod phrases;
 mod random_word_api;
+mod random_word_query;
+mod translation;
 
+pub use builder::DictionaryBuilder;
 pub use dictionary::{PartOfSpeech, Phonetic, Word, WordDefinition, WordMeaning};
+pub use error::{DictionaryError, MalformedPayload, NotFoundError, TransportError};
+pub use frequency::{frequency_rank, words_in_levels, Level};
+pub use http::HttpConfig;
+pub use language::Language;
+pub use phrases::{is_phrase, phrases};
+pub use morphology::{inflect, inflections, lemma_candidates, lemmatize, normalize, same_lemma, Inflection};
+pub use random_word_query::{RandomWordBackend, RandomWordQuery};
+pub use translation::{TranslationBackend, TranslationFile};
+use http::HttpClient;
+use phrases::lookup_phrase;
 use random_word_api::get_random_words;
 
-#[derive(Debug)]
-pub enum DictionaryError {
-    Fetch(reqwest::Error),
-    Deserialize(reqwest::Error),
-    Conversion(UnknownPartOfSpeech),
-    NotFound(NotFoundError),
+pub struct Dictionary {
+    client: HttpClient,
+    dictionary_url: String,
+    random_word_url: String,
+    random_word_backend: RandomWordBackend,
+    language: Language,
+    translations: Option<Arc<dyn TranslationBackend>>,
 }
 
-impl Error for DictionaryError {}
-
-impl Display for DictionaryError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            DictionaryError::Fetch(error) => {
-                f.write_str("Faild to fetch from the dictionary: ")?;
-                f.write_fmt(format_args!("{error}"))
-            },
-            DictionaryError::Deserialize(error) => {
-                f.write_str("Faild to deserialize data from the dictionary: ")?;
-                f.write_fmt(format_args!("{error}"))
-            },
-            DictionaryError::Conversion(error) => {
-                f.write_fmt(format_args!("Couldn't process part of speech '{}'.", error.kind))
-            },
-            DictionaryError::NotFound(error) => {
-                f.write_fmt(format_args!("Not found: {}.", error.message))
-            },
-        }
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-#[derive(Debug)]
-pub struct NotFoundError {
-    pub message: String,
-}
-
-pub struct Dictionary {
-    client: reqwest::Client,
-}
-
 impl Dictionary {
+    /// Creates a dictionary with the default timeouts, retries and rate limit
+    ///
+    /// # Panics
+    /// Panics when the http client can't be created, like `reqwest::Client::new`
     pub fn new() -> Self {
-        Self {
-            client: reqwest::Client::new(),
-        }
+        Self::with_config(HttpConfig::default()).expect("Failed to create the http client")
+    }
+
+    pub fn with_config(config: HttpConfig) -> Result<Self, DictionaryError> {
+        Self::builder().config(config).build()
+    }
+
+    pub fn builder() -> DictionaryBuilder {
+        DictionaryBuilder::new()
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
     }
 
+    /// The backend translating looked up words, None when translations aren't configured
+    pub fn translations(&self) -> Option<&dyn TranslationBackend> {
+        self.translations.as_deref()
+    }
+
+    /// The languages both the dictionary api and the random word backend support
+    pub fn supported_languages(&self) -> Vec<Language> {
+        Self::languages_supported_by(&self.dictionary_url, self.random_word_backend)
+    }
+
+    pub(crate) fn languages_supported_by(dictionary_url: &str, random_word_backend: RandomWordBackend) -> Vec<Language> {
+        dictionary_api_languages(dictionary_url)
+            .iter()
+            .filter(|language| random_word_backend.supported_languages().contains(language))
+            .copied()
+            .collect()
+    }
+
+    /// Looks up a word or a phrase. English idioms and phrasal verbs the api rarely knows are taken from a bundled list first.
+    /// The translations of the word are attached when a translation backend is configured.
     pub async fn get_definition(&self, word: &str) -> Result<Word, DictionaryError> {
-        get_definition(&self.client, word).await
+        let phrase = (self.language == Language::English && is_phrase(word))
+            .then(|| lookup_phrase(word))
+            .flatten();
+        let mut word = match phrase {
+            Some(phrase) => phrase,
+            None => get_definition(&self.client, &self.dictionary_url, word, self.language).await?,
+        };
+        if let Some(translations) = &self.translations {
+            word.translations = translations.translate(&word.word);
+        }
+        Ok(word)
     }
+
+    /// Looks up the lemma of a word, so `geese` is defined as `goose`. When the lemma is unknown
+    /// the word itself and then the other possible lemmas are tried.
+    /// Lemmas are only known for english, words of other languages are looked up as they are.
+    pub async fn get_lemma_definition(&self, word: &str) -> Result<Word, DictionaryError> {
+        let word = normalize(word);
+        if self.language != Language::English {
+            return self.get_definition(&word).await;
+        }
+        let mut attempts = vec![lemmatize(&word)];
+        for attempt in [word.clone()].into_iter().chain(lemma_candidates(&word).into_iter().take(MAX_LEMMA_ATTEMPTS)) {
+            if !attempts.contains(&attempt) {
+                attempts.push(attempt);
+            }
+        }
+        let mut result = self.get_definition(&attempts[0]).await;
+        for attempt in &attempts[1..] {
+            if !matches!(result, Err(DictionaryError::NotFound(_))) {
+                break;
+            }
+            result = self.get_definition(attempt).await;
+        }
+        result
+    }
+
+    /// Fetches a random word, optionally of the given length
     pub async fn get_random_word(
         &self,
         length: Option<usize>,
     ) -> Result<Vec<String>, DictionaryError> {
-        get_random_words(&self.client, 1, length).await
+        self.get_random_words(1, length).await
     }
+    /// Fetches `max` distinct random words, optionally of the given length
     pub async fn get_random_words(
         &self,
         max: usize,
         length: Option<usize>,
     ) -> Result<Vec<String>, DictionaryError> {
-        get_random_words(&self.client, max, length).await
+        let mut query = RandomWordQuery::new(max);
+        if let Some(length) = length {
+            query = query.length(length);
+        }
+        self.random_words(&query).await
+    }
+
+    /// Fetches `query.count()` distinct random words matching the query from the configured backend.
+    /// More words are fetched until there are enough of them, failing with `NotEnoughWords` once the attempts run out.
+    pub async fn random_words(&self, query: &RandomWordQuery) -> Result<Vec<String>, DictionaryError> {
+        let lookup = query.part_of_speech.is_some();
+        let words = self.collect_random_words(query, lookup).await?;
+        Ok(words.into_iter().map(|(word, _)| word).collect())
+    }
+
+    /// Like `random_words`, but every word is looked up and only words the dictionary can define are returned
+    pub async fn random_definitions(&self, query: &RandomWordQuery) -> Result<Vec<Word>, DictionaryError> {
+        let words = self.collect_random_words(query, true).await?;
+        Ok(words.into_iter().filter_map(|(_, definition)| definition).collect())
+    }
+
+    /// Collects distinct matching random words with their definitions when `lookup` is set
+    async fn collect_random_words(
+        &self,
+        query: &RandomWordQuery,
+        lookup: bool,
+    ) -> Result<Vec<(String, Option<Word>)>, DictionaryError> {
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        let mut rng = query.rng();
+        for _ in 0..MAX_RANDOM_WORD_ATTEMPTS {
+            let missing = query.count - found.len();
+            if missing == 0 {
+                break;
+            }
+            let candidates = self
+                .fetch_random_batch(query, missing, lookup, &mut rng)
+                .await?
+                .into_iter()
+                .filter(|word| query.matches(word) && seen.insert(word.to_lowercase()))
+                .collect::<Vec<String>>();
+            if !lookup {
+                found.extend(candidates.into_iter().take(missing).map(|word| (word, None)));
+                continue;
+            }
+            let mut definitions = pin!(stream::iter(candidates)
+                .map(|word| async move { (self.get_definition(&word).await, word) })
+                .buffered(DEFINITION_LOOKUP_CONCURRENCY));
+            while let Some((definition, word)) = definitions.next().await {
+                let definition = match definition {
+                    Ok(definition) => definition,
+                    // the random word apis know many words the dictionary doesn't
+                    Err(DictionaryError::NotFound(_)) => continue,
+                    Err(error) => return Err(error),
+                };
+                let matches_part_of_speech = query.part_of_speech.as_ref().is_none_or(|part_of_speech| {
+                    definition
+                        .meanings
+                        .iter()
+                        .any(|meaning| meaning.part_of_speech == *part_of_speech)
+                });
+                if matches_part_of_speech {
+                    found.push((word, Some(definition)));
+                    if found.len() == query.count {
+                        break;
+                    }
+                }
+            }
+        }
+        if found.len() < query.count {
+            return Err(DictionaryError::NotEnoughWords {
+                requested: query.count,
+                found: found.len(),
+            });
+        }
+        Ok(found)
+    }
+
+    /// Fetches a batch of random words of which `missing` are still needed
+    async fn fetch_random_batch(
+        &self,
+        query: &RandomWordQuery,
+        missing: usize,
+        lookup: bool,
+        rng: &mut ChaCha8Rng,
+    ) -> Result<Vec<String>, DictionaryError> {
+        let backend = self.random_word_backend;
+        match backend {
+            RandomWordBackend::RandomWordApi => {
+                let count = if lookup || query.filters_after_fetching(backend) {
+                    missing * FILTERED_FETCH_FACTOR
+                } else {
+                    missing
+                };
+                get_random_words(
+                    &self.client,
+                    &self.random_word_url,
+                    count,
+                    query.backend_length(backend),
+                    query.backend_first_letter(backend),
+                    self.language,
+                )
+                .await
+            }
+            RandomWordBackend::FrequencyList => {
+                let mut words = words_in_levels(Level::A1..=Level::C1)
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect::<Vec<String>>();
+                words.shuffle(rng);
+                Ok(words)
+            }
+        }
     }
 }
+
+/// How many more words are fetched when some of them will be filtered out afterwards
+const FILTERED_FETCH_FACTOR: usize = 4;
+/// How many definitions of random words are looked up at once
+const DEFINITION_LOOKUP_CONCURRENCY: usize = 4;
+/// How many guessed lemmas are looked up before a word counts as not found
+const MAX_LEMMA_ATTEMPTS: usize = 3;
+/// How often random words are fetched before giving up on finding enough matching ones
+const MAX_RANDOM_WORD_ATTEMPTS: usize = 5;
@@ -0,0 +1,330 @@
+use crate::{frequency::is_listed, phrases::is_listed_phrase};
+
+/// A grammatical form of a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Inflection {
+    Plural,
+    Past,
+    PastParticiple,
+    /// The -ing form
+    PresentParticiple,
+    Comparative,
+    Superlative,
+}
+
+impl Inflection {
+    pub const ALL: [Inflection; 6] = [
+        Inflection::Plural,
+        Inflection::Past,
+        Inflection::PastParticiple,
+        Inflection::PresentParticiple,
+        Inflection::Comparative,
+        Inflection::Superlative,
+    ];
+}
+
+/// Forms that don't follow the spelling rules, with their lemma
+const IRREGULAR_FORMS: &[(&str, &str, Inflection)] = &[
+    ("men", "man", Inflection::Plural),
+    ("women", "woman", Inflection::Plural),
+    ("children", "child", Inflection::Plural),
+    ("people", "person", Inflection::Plural),
+    ("feet", "foot", Inflection::Plural),
+    ("teeth", "tooth", Inflection::Plural),
+    ("geese", "goose", Inflection::Plural),
+    ("mice", "mouse", Inflection::Plural),
+    ("oxen", "ox", Inflection::Plural),
+    ("lives", "life", Inflection::Plural),
+    ("knives", "knife", Inflection::Plural),
+    ("wives", "wife", Inflection::Plural),
+    ("leaves", "leaf", Inflection::Plural),
+    ("halves", "half", Inflection::Plural),
+    ("shelves", "shelf", Inflection::Plural),
+    ("wolves", "wolf", Inflection::Plural),
+    ("thieves", "thief", Inflection::Plural),
+    ("was", "be", Inflection::Past),
+    ("were", "be", Inflection::Past),
+    ("been", "be", Inflection::PastParticiple),
+    ("had", "have", Inflection::Past),
+    ("had", "have", Inflection::PastParticiple),
+    ("did", "do", Inflection::Past),
+    ("done", "do", Inflection::PastParticiple),
+    ("went", "go", Inflection::Past),
+    ("gone", "go", Inflection::PastParticiple),
+    ("ran", "run", Inflection::Past),
+    ("run", "run", Inflection::PastParticiple),
+    ("came", "come", Inflection::Past),
+    ("come", "come", Inflection::PastParticiple),
+    ("saw", "see", Inflection::Past),
+    ("seen", "see", Inflection::PastParticiple),
+    ("took", "take", Inflection::Past),
+    ("taken", "take", Inflection::PastParticiple),
+    ("made", "make", Inflection::Past),
+    ("made", "make", Inflection::PastParticiple),
+    ("said", "say", Inflection::Past),
+    ("said", "say", Inflection::PastParticiple),
+    ("got", "get", Inflection::Past),
+    ("gotten", "get", Inflection::PastParticiple),
+    ("knew", "know", Inflection::Past),
+    ("known", "know", Inflection::PastParticiple),
+    ("thought", "think", Inflection::Past),
+    ("thought", "think", Inflection::PastParticiple),
+    ("gave", "give", Inflection::Past),
+    ("given", "give", Inflection::PastParticiple),
+    ("found", "find", Inflection::Past),
+    ("found", "find", Inflection::PastParticiple),
+    ("told", "tell", Inflection::Past),
+    ("told", "tell", Inflection::PastParticiple),
+    ("felt", "feel", Inflection::Past),
+    ("felt", "feel", Inflection::PastParticiple),
+    ("brought", "bring", Inflection::Past),
+    ("brought", "bring", Inflection::PastParticiple),
+    ("began", "begin", Inflection::Past),
+    ("begun", "begin", Inflection::PastParticiple),
+    ("kept", "keep", Inflection::Past),
+    ("kept", "keep", Inflection::PastParticiple),
+    ("wrote", "write", Inflection::Past),
+    ("written", "write", Inflection::PastParticiple),
+    ("stood", "stand", Inflection::Past),
+    ("stood", "stand", Inflection::PastParticiple),
+    ("spoke", "speak", Inflection::Past),
+    ("spoken", "speak", Inflection::PastParticiple),
+    ("grew", "grow", Inflection::Past),
+    ("grown", "grow", Inflection::PastParticiple),
+    ("lost", "lose", Inflection::Past),
+    ("lost", "lose", Inflection::PastParticiple),
+    ("fell", "fall", Inflection::Past),
+    ("fallen", "fall", Inflection::PastParticiple),
+    ("broke", "break", Inflection::Past),
+    ("broken", "break", Inflection::PastParticiple),
+    ("drove", "drive", Inflection::Past),
+    ("driven", "drive", Inflection::PastParticiple),
+    ("bought", "buy", Inflection::Past),
+    ("bought", "buy", Inflection::PastParticiple),
+    ("chose", "choose", Inflection::Past),
+    ("chosen", "choose", Inflection::PastParticiple),
+    ("caught", "catch", Inflection::Past),
+    ("caught", "catch", Inflection::PastParticiple),
+    ("taught", "teach", Inflection::Past),
+    ("taught", "teach", Inflection::PastParticiple),
+    ("ate", "eat", Inflection::Past),
+    ("eaten", "eat", Inflection::PastParticiple),
+    ("flew", "fly", Inflection::Past),
+    ("flown", "fly", Inflection::PastParticiple),
+    ("swam", "swim", Inflection::Past),
+    ("swum", "swim", Inflection::PastParticiple),
+    ("sang", "sing", Inflection::Past),
+    ("sung", "sing", Inflection::PastParticiple),
+    ("threw", "throw", Inflection::Past),
+    ("thrown", "throw", Inflection::PastParticiple),
+    ("better", "good", Inflection::Comparative),
+    ("best", "good", Inflection::Superlative),
+    ("worse", "bad", Inflection::Comparative),
+    ("worst", "bad", Inflection::Superlative),
+    ("further", "far", Inflection::Comparative),
+    ("furthest", "far", Inflection::Superlative),
+];
+
+/// Suffixes that are removed to find the lemma of an inflected word, with what replaces them
+const SUFFIX_RULES: &[(&str, &[&str])] = &[
+    ("ies", &["y"]),
+    ("ied", &["y"]),
+    ("ier", &["y"]),
+    ("iest", &["y"]),
+    ("ying", &["ie"]),
+    ("es", &["", "e"]),
+    ("s", &[""]),
+    ("ed", &["", "e"]),
+    ("ing", &["", "e"]),
+    ("er", &["", "e"]),
+    ("est", &["", "e"]),
+];
+
+/// Lemmas are never shorter than this
+const MIN_LEMMA_LENGTH: usize = 2;
+
+/// Trims the word, lowercases it and collapses the whitespace between the words of a phrase
+pub fn normalize(word: &str) -> String {
+    word.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Every lemma the word could be an inflection of, the most likely ones first. The word itself isn't included.
+/// Only irregular forms are certain, the others are guesses from the spelling.
+pub fn lemma_candidates(word: &str) -> Vec<String> {
+    let word = normalize(word);
+    if let Some((head, rest)) = word.split_once(' ') {
+        // only the first word of a phrase is inflected, `took after` is a form of `take after`
+        let mut candidates = lemma_candidates(head)
+            .into_iter()
+            .map(|head| format!("{head} {rest}"))
+            .collect::<Vec<String>>();
+        candidates.sort_by_key(|candidate| !is_known(candidate));
+        return candidates;
+    }
+    let irregular = IRREGULAR_FORMS
+        .iter()
+        .filter(|(form, lemma, _)| *form == word && *lemma != word)
+        .map(|(_, lemma, _)| (*lemma).to_owned());
+    let guessed = SUFFIX_RULES
+        .iter()
+        // words like `glass` don't end in an inflection
+        .filter(|(suffix, _)| !(*suffix == "s" && word.ends_with("ss")))
+        .filter_map(|(suffix, replacements)| Some((word.strip_suffix(suffix)?, *replacements)))
+        .flat_map(|(stem, replacements)| {
+            let undoubled = undouble(stem);
+            replacements
+                .iter()
+                .map(move |replacement| format!("{stem}{replacement}"))
+                .chain(undoubled)
+        })
+        .filter(|lemma| lemma.chars().count() >= MIN_LEMMA_LENGTH);
+    let mut candidates = Vec::new();
+    for candidate in irregular.chain(guessed) {
+        if candidate != word && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    // guesses that are known words are much more likely
+    candidates.sort_by_key(|candidate| !is_irregular_lemma(&word, candidate) && !is_known(candidate));
+    candidates
+}
+
+/// The dictionary form of a word, like `goose` for `geese` or `run` for `running`.
+/// Words in the frequency list and listed phrases are lemmas themselves, for other words the most likely known lemma is used.
+/// Words without a known lemma are only normalized.
+pub fn lemmatize(word: &str) -> String {
+    let word = normalize(word);
+    if is_known(&word) {
+        return word;
+    }
+    lemma_candidates(&word)
+        .into_iter()
+        .find(|candidate| is_irregular_lemma(&word, candidate) || is_known(candidate))
+        .unwrap_or(word)
+}
+
+/// The given form of a lemma, irregular forms are looked up and the others follow the spelling rules
+pub fn inflect(lemma: &str, inflection: Inflection) -> String {
+    let lemma = normalize(lemma);
+    if let Some((form, _, _)) = IRREGULAR_FORMS
+        .iter()
+        .find(|(_, known, kind)| *known == lemma && *kind == inflection)
+    {
+        return (*form).to_owned();
+    }
+    if lemma.contains(' ') {
+        // only the first word of a phrase is inflected, like `took after`
+        let (head, rest) = lemma.split_once(' ').unwrap_or((&lemma, ""));
+        return format!("{} {rest}", inflect(head, inflection));
+    }
+    match inflection {
+        Inflection::Plural => {
+            if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| lemma.ends_with(suffix)) {
+                format!("{lemma}es")
+            } else {
+                with_suffix(&lemma, "s")
+            }
+        }
+        Inflection::Past | Inflection::PastParticiple => with_suffix(&lemma, "ed"),
+        Inflection::PresentParticiple => match lemma.strip_suffix("ie") {
+            Some(stem) => format!("{stem}ying"),
+            None => with_suffix(&lemma, "ing"),
+        },
+        Inflection::Comparative => with_suffix(&lemma, "er"),
+        Inflection::Superlative => with_suffix(&lemma, "est"),
+    }
+}
+
+/// Every inflected form of a lemma, some of them may not exist, like the plural of an adjective
+pub fn inflections(lemma: &str) -> Vec<String> {
+    let mut forms = Vec::new();
+    for inflection in Inflection::ALL {
+        let form = inflect(lemma, inflection);
+        if !forms.contains(&form) {
+            forms.push(form);
+        }
+    }
+    forms
+}
+
+/// Whether both words are forms of the same lemma, ignoring case
+pub fn same_lemma(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    a == b || lemmatize(&a) == lemmatize(&b) || is_inflection_of(&a, &b) || is_inflection_of(&b, &a)
+}
+
+/// Listed words are lemmas of their own, `flower` isn't a form of `flow`
+fn is_inflection_of(form: &str, lemma: &str) -> bool {
+    !is_known(form) && inflections(lemma).iter().any(|inflection| inflection == form)
+}
+
+/// Words of the frequency list and listed phrases are known lemmas
+fn is_known(word: &str) -> bool {
+    is_listed(word) || is_listed_phrase(word)
+}
+
+fn is_irregular_lemma(form: &str, lemma: &str) -> bool {
+    IRREGULAR_FORMS
+        .iter()
+        .any(|(known_form, known_lemma, _)| *known_form == form && *known_lemma == lemma)
+}
+
+fn is_vowel(letter: char) -> bool {
+    matches!(letter, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Short words ending in a consonant after a single vowel double it before a suffix, like `stop` and `stopped`
+fn doubles_final_consonant(word: &str) -> bool {
+    let letters = word.chars().collect::<Vec<char>>();
+    let vowel_groups = letters
+        .iter()
+        .enumerate()
+        .filter(|(index, letter)| is_vowel(**letter) && (*index == 0 || !is_vowel(letters[index - 1])))
+        .count();
+    match letters[..] {
+        [.., before, vowel, last] => {
+            vowel_groups == 1
+                && !is_vowel(before)
+                && is_vowel(vowel)
+                && !is_vowel(last)
+                && !matches!(last, 'w' | 'x' | 'y')
+        }
+        _ => false,
+    }
+}
+
+/// Removes a doubled final consonant, `runn` becomes `run`
+fn undouble(stem: &str) -> Option<String> {
+    let mut letters = stem.chars().rev();
+    match (letters.next(), letters.next()) {
+        (Some(last), Some(before)) if last == before && !is_vowel(last) && !matches!(last, 'l' | 's') => {
+            Some(stem[..stem.len() - last.len_utf8()].to_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Appends a suffix starting with a vowel or `s`, following the usual spelling changes
+fn with_suffix(word: &str, suffix: &str) -> String {
+    let mut letters = word.chars().rev();
+    let (last, before) = (letters.next(), letters.next());
+    match (before, last) {
+        // study, studies, studied but not studiing
+        (Some(before), Some('y')) if !is_vowel(before) && suffix != "ing" => {
+            let stem = &word[..word.len() - 1];
+            match suffix {
+                "s" => format!("{stem}ies"),
+                _ => format!("{stem}i{suffix}"),
+            }
+        }
+        // make, making, made but not see, seing
+        (Some(before), Some('e')) if suffix != "s" && !matches!(before, 'e' | 'o' | 'y') => {
+            format!("{}{suffix}", &word[..word.len() - 1])
+        }
+        (_, Some(last)) if suffix != "s" && doubles_final_consonant(word) => format!("{word}{last}{suffix}"),
+        _ => format!("{word}{suffix}"),
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::OnceLock;
+
+use crate::{morphology::normalize, Language, PartOfSpeech, Word, WordDefinition, WordMeaning};
+
+/// Common english idioms and phrasal verbs with their definitions
+const PHRASE_LIST: &str = include_str!("../data/phrases.txt");
+
+struct PhraseEntry {
+    phrase: &'static str,
+    part_of_speech: PartOfSpeech,
+    definition: &'static str,
+    example: Option<&'static str>,
+    synonyms: Vec<&'static str>,
+}
+
+fn entries() -> &'static [PhraseEntry] {
+    static ENTRIES: OnceLock<Vec<PhraseEntry>> = OnceLock::new();
+    ENTRIES.get_or_init(|| {
+        PHRASE_LIST
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut fields = line.split('|').map(str::trim);
+                let mut next = || fields.next().unwrap_or_default();
+                let phrase = next();
+                let part_of_speech = PartOfSpeech::try_from(next().to_owned())
+                    .unwrap_or_else(|error| panic!("Unknown part of speech '{}' of '{phrase}'", error.kind));
+                PhraseEntry {
+                    phrase,
+                    part_of_speech,
+                    definition: next(),
+                    example: Some(next()).filter(|example| !example.is_empty()),
+                    synonyms: next()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|synonym| !synonym.is_empty())
+                        .collect(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Whether the text is made of more than one word, like `red herring` or `take after`
+pub fn is_phrase(text: &str) -> bool {
+    normalize(text).contains(' ')
+}
+
+/// Whether the phrase is in the bundled list
+pub(crate) fn is_listed_phrase(phrase: &str) -> bool {
+    entries().iter().any(|entry| entry.phrase == phrase)
+}
+
+/// Every bundled phrase, in no particular order
+pub fn phrases() -> Vec<&'static str> {
+    entries().iter().map(|entry| entry.phrase).collect()
+}
+
+/// Looks the phrase up in the bundled list, None when it isn't listed
+pub(crate) fn lookup_phrase(phrase: &str) -> Option<Word> {
+    let phrase = normalize(phrase);
+    let meanings = entries()
+        .iter()
+        .filter(|entry| entry.phrase == phrase)
+        .map(|entry| WordMeaning {
+            part_of_speech: entry.part_of_speech.clone(),
+            definitions: vec![WordDefinition {
+                definition: entry.definition.to_owned(),
+                example: entry.example.map(str::to_owned),
+                synonyms: Vec::new(),
+                antonyms: Vec::new(),
+            }],
+            synonyms: entry.synonyms.iter().map(|synonym| (*synonym).to_owned()).collect(),
+            antonyms: Vec::new(),
+        })
+        .collect::<Vec<WordMeaning>>();
+    if meanings.is_empty() {
+        return None;
+    }
+    Some(Word {
+        word: phrase,
+        language: Language::English,
+        phonetic: None,
+        phonetics: Vec::new(),
+        origin: None,
+        meanings,
+        translations: Vec::new(),
+    })
+}
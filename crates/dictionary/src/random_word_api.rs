@@ -2,23 +2,58 @@
 // https://random-word-api.vercel.app/ - amount, length, first letter
 // https://random-word.ryanrk.com/ - amount, length(minmax) // bad because the words are weird
 
-use crate::DictionaryError;
+use crate::{
+    http::{read_json, unexpected_status, HttpClient},
+    DictionaryError, Language,
+};
 
-const RANDOM_WORD_API_URL: &'static str = "https://random-word-api.vercel.app/api";
+pub(crate) const DEFAULT_RANDOM_WORD_API_URL: &str = "https://random-word-api.vercel.app";
+
+/// The languages random-word-api has word lists for
+pub(crate) const RANDOM_WORD_API_LANGUAGES: &[Language] = &[
+    Language::English,
+    Language::Spanish,
+    Language::French,
+    Language::German,
+    Language::Italian,
+    Language::Portuguese,
+];
+
+/// The `lang` parameter of a language, english is the default and isn't sent
+fn language_parameter(language: Language) -> Option<&'static str> {
+    match language {
+        Language::English => None,
+        Language::Portuguese => Some("pt-br"),
+        language => Some(language.code()),
+    }
+}
 
 pub(crate) async fn get_random_words(
-    client: &reqwest::Client,
+    client: &HttpClient,
+    base_url: &str,
     max: usize,
     length: Option<usize>,
+    first_letter: Option<char>,
+    language: Language,
 ) -> Result<Vec<String>, DictionaryError> {
-    let mut req = client
-        .get(RANDOM_WORD_API_URL)
-        .query(&[("words", max)]);
+    let mut query = vec![("words", max.to_string())];
     if let Some(length) = length {
-        req = req.query(&[("length", length)]);
+        query.push(("length", length.to_string()));
+    }
+    if let Some(letter) = first_letter {
+        query.push(("letter", letter.to_string()));
+    }
+    if let Some(language) = language_parameter(language) {
+        query.push(("lang", language.to_owned()));
+    }
+    let url = format!("{}/api", base_url.trim_end_matches('/'));
+    let res: reqwest::Response = client.get(&url, &query).await?;
+    if !res.status().is_success() {
+        return Err(unexpected_status(res).await);
+    }
+    let words: Vec<String> = read_json(res).await?;
+    if words.is_empty() && max > 0 {
+        return Err(DictionaryError::EmptyResponse);
     }
-    let res: reqwest::Response = req.send().await.map_err(DictionaryError::Fetch)?;
-    res.json::<Vec<String>>()
-        .await
-        .map_err(DictionaryError::Deserialize)
+    Ok(words)
 }
@@ -0,0 +1,164 @@
+use std::ops::RangeInclusive;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{random_word_api::RANDOM_WORD_API_LANGUAGES, Language, PartOfSpeech};
+
+/// Where random words come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RandomWordBackend {
+    /// A random-word-api compatible api, it can filter by exact length and first letter itself
+    #[default]
+    RandomWordApi,
+    /// The bundled list of common words, works offline but only knows a few thousand words
+    FrequencyList,
+}
+
+impl RandomWordBackend {
+    /// The languages the backend has random words for
+    pub fn supported_languages(&self) -> &'static [Language] {
+        match self {
+            RandomWordBackend::RandomWordApi => RANDOM_WORD_API_LANGUAGES,
+            RandomWordBackend::FrequencyList => &[Language::English],
+        }
+    }
+
+    fn filters_exact_length(&self) -> bool {
+        matches!(self, RandomWordBackend::RandomWordApi)
+    }
+
+    fn filters_first_letter(&self) -> bool {
+        matches!(self, RandomWordBackend::RandomWordApi)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LengthFilter {
+    Exact(usize),
+    Range(RangeInclusive<usize>),
+}
+
+impl LengthFilter {
+    fn contains(&self, length: usize) -> bool {
+        match self {
+            LengthFilter::Exact(exact) => length == *exact,
+            LengthFilter::Range(range) => range.contains(&length),
+        }
+    }
+}
+
+/// Describes which random words to fetch.
+/// Filters a backend doesn't support are applied after fetching, which may need more words to be fetched.
+///
+/// ```
+/// # use dictionary::{RandomWordQuery, PartOfSpeech};
+/// let query = RandomWordQuery::new(4)
+///     .length_range(5..=8)
+///     .first_letter('s')
+///     .part_of_speech(PartOfSpeech::Verb)
+///     .exclude(["swim"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RandomWordQuery {
+    pub(crate) count: usize,
+    pub(crate) length: Option<LengthFilter>,
+    pub(crate) first_letter: Option<char>,
+    pub(crate) part_of_speech: Option<PartOfSpeech>,
+    pub(crate) exclude: Vec<String>,
+    pub(crate) seed: Option<u64>,
+}
+
+impl RandomWordQuery {
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            ..Default::default()
+        }
+    }
+
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = Some(LengthFilter::Exact(length));
+        self
+    }
+
+    pub fn length_range(mut self, range: RangeInclusive<usize>) -> Self {
+        self.length = Some(LengthFilter::Range(range));
+        self
+    }
+
+    pub fn first_letter(mut self, letter: char) -> Self {
+        self.first_letter = Some(letter.to_ascii_lowercase());
+        self
+    }
+
+    /// Only words that can be used as this part of speech, checking it needs a dictionary lookup for every word
+    pub fn part_of_speech(mut self, part_of_speech: PartOfSpeech) -> Self {
+        self.part_of_speech = Some(part_of_speech);
+        self
+    }
+
+    /// Words that must not be returned, compared ignoring case
+    pub fn exclude<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.exclude
+            .extend(words.into_iter().map(|word| word.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Makes the words picked from the frequency list reproducible, the random word api can't be seeded
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The rng the frequency list is shuffled with, seeded when the query is.
+    /// ChaCha gives the same words for a seed on every platform and rand version
+    pub(crate) fn rng(&self) -> ChaCha8Rng {
+        match self.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        }
+    }
+
+    /// The exact length the backend should filter by, if it can
+    pub(crate) fn backend_length(&self, backend: RandomWordBackend) -> Option<usize> {
+        match self.length {
+            Some(LengthFilter::Exact(length)) if backend.filters_exact_length() => Some(length),
+            _ => None,
+        }
+    }
+
+    /// The first letter the backend should filter by, if it can
+    pub(crate) fn backend_first_letter(&self, backend: RandomWordBackend) -> Option<char> {
+        self.first_letter
+            .filter(|_| backend.filters_first_letter())
+    }
+
+    /// Whether some words the backend returns may still be filtered out afterwards
+    pub(crate) fn filters_after_fetching(&self, backend: RandomWordBackend) -> bool {
+        self.part_of_speech.is_some()
+            || !self.exclude.is_empty()
+            || (self.length.is_some() && self.backend_length(backend).is_none())
+            || (self.first_letter.is_some() && self.backend_first_letter(backend).is_none())
+    }
+
+    /// Checks every filter except the part of speech, which needs a definition
+    pub(crate) fn matches(&self, word: &str) -> bool {
+        let lowercase = word.to_lowercase();
+        self.length
+            .as_ref()
+            .is_none_or(|length| length.contains(word.chars().count()))
+            && self
+                .first_letter
+                .is_none_or(|letter| lowercase.starts_with(letter))
+            && !self.exclude.contains(&lowercase)
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use crate::{morphology::normalize, Language};
+
+/// Translates the words of one language into the native language of the learner
+pub trait TranslationBackend: Send + Sync {
+    /// The language of the words that are translated, the one being learned
+    fn source_language(&self) -> Language;
+
+    /// The language the words are translated into
+    fn target_language(&self) -> Language;
+
+    /// The translations of a word, the most common first. Empty when the word isn't known
+    fn translate(&self, word: &str) -> Vec<String>;
+
+    /// The words that have the translation among their translations
+    fn words_translated_as(&self, translation: &str) -> Vec<String>;
+
+    /// Every known translation, in no particular order
+    fn all_translations(&self) -> Vec<String>;
+}
+
+/// Translations read from a local file, so they work offline.
+///
+/// Every line has a word followed by its comma separated translations, e.g. `ephemeral|efímero, pasajero`.
+/// Empty lines and lines starting with `#` are skipped.
+#[derive(Debug, Clone)]
+pub struct TranslationFile {
+    source_language: Language,
+    target_language: Language,
+    translations: HashMap<String, Vec<String>>,
+}
+
+impl TranslationFile {
+    pub fn open(path: impl AsRef<Path>, source_language: Language, target_language: Language) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?, source_language, target_language)
+    }
+
+    /// Fails with `InvalidData` on a line without translations
+    pub fn parse(contents: &str, source_language: Language, target_language: Language) -> io::Result<Self> {
+        let mut translations: HashMap<String, Vec<String>> = HashMap::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid_line = || io::Error::new(ErrorKind::InvalidData, format!("line {}: '{line}'", index + 1));
+            let (word, translated) = line.split_once('|').ok_or_else(invalid_line)?;
+            let word = normalize(word);
+            let translated = translated
+                .split(',')
+                .map(normalize)
+                .filter(|translation| !translation.is_empty())
+                .collect::<Vec<String>>();
+            if word.is_empty() || translated.is_empty() {
+                return Err(invalid_line());
+            }
+            // a word listed twice keeps the translations of both lines
+            let known = translations.entry(word).or_default();
+            for translation in translated {
+                if !known.contains(&translation) {
+                    known.push(translation);
+                }
+            }
+        }
+        Ok(Self {
+            source_language,
+            target_language,
+            translations,
+        })
+    }
+
+    /// How many words have translations
+    pub fn len(&self) -> usize {
+        self.translations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.translations.is_empty()
+    }
+}
+
+impl TranslationBackend for TranslationFile {
+    fn source_language(&self) -> Language {
+        self.source_language
+    }
+
+    fn target_language(&self) -> Language {
+        self.target_language
+    }
+
+    fn translate(&self, word: &str) -> Vec<String> {
+        self.translations.get(&normalize(word)).cloned().unwrap_or_default()
+    }
+
+    fn words_translated_as(&self, translation: &str) -> Vec<String> {
+        let translation = normalize(translation);
+        let mut words = self
+            .translations
+            .iter()
+            .filter(|(_, translations)| translations.contains(&translation))
+            .map(|(word, _)| word.clone())
+            .collect::<Vec<String>>();
+        words.sort();
+        words
+    }
+
+    fn all_translations(&self) -> Vec<String> {
+        let mut translations = self.translations.values().flatten().cloned().collect::<Vec<String>>();
+        translations.sort();
+        translations.dedup();
+        translations
+    }
+}
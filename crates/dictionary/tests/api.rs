@@ -0,0 +1,666 @@
+mod common;
+
+use std::{
+    error::Error,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use common::{fixture, MockResponse, MockServer};
+use dictionary::{
+    Dictionary, DictionaryError, HttpConfig, Language, PartOfSpeech, RandomWordBackend, RandomWordQuery,
+    TranslationFile,
+};
+use reqwest::header::{HeaderName, HeaderValue};
+
+fn test_config() -> HttpConfig {
+    HttpConfig {
+        timeout: Duration::from_secs(2),
+        connect_timeout: Duration::from_secs(1),
+        max_retries: 2,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+        min_request_interval: Duration::ZERO,
+    }
+}
+
+fn dictionary(server: &MockServer, config: HttpConfig) -> Dictionary {
+    Dictionary::builder()
+        .dictionary_url(format!("{}/api/v2", server.url))
+        .random_word_url(&server.url)
+        .config(config)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn parses_a_recorded_definition() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/ephemeral",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    let word = dictionary(&server, test_config())
+        .get_definition("ephemeral")
+        .await
+        .unwrap();
+
+    assert_eq!(word.word, "ephemeral");
+    assert_eq!(word.meanings.len(), 2);
+    assert_eq!(word.meanings[1].part_of_speech, PartOfSpeech::Adjective);
+    assert_eq!(
+        word.meanings[1].definitions[0].example.as_deref(),
+        Some("Youth is ephemeral.")
+    );
+    assert_eq!(
+        word.all_synonyms().collect::<Vec<&str>>(),
+        ["fleeting", "short-lived", "transient"]
+    );
+    assert_eq!(word.phonetics[0].audio, None);
+}
+
+#[tokio::test]
+async fn reports_unknown_words_as_not_found() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/qwertyuiop",
+        [MockResponse::fixture(404, "not_found.json")],
+    );
+    let result = dictionary(&server, test_config())
+        .get_definition("qwertyuiop")
+        .await;
+
+    assert!(matches!(result, Err(DictionaryError::NotFound(_))));
+    // a 404 is an answer, not a transient failure
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn percent_encodes_looked_up_phrases() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/kick%20the%20can",
+        [MockResponse::fixture(404, "not_found.json")],
+    );
+    let result = dictionary(&server, test_config())
+        .get_definition("kick the can")
+        .await;
+
+    assert!(matches!(result, Err(DictionaryError::NotFound(_))));
+    assert_eq!(server.requests()[0].path, "/api/v2/entries/en/kick%20the%20can");
+}
+
+#[tokio::test]
+async fn defines_bundled_phrases_without_the_api() {
+    let server = MockServer::start().await;
+    let dict = dictionary(&server, test_config());
+    let word = dict.get_definition("Red  Herring").await.unwrap();
+
+    assert_eq!(word.word, "red herring");
+    assert_eq!(word.meanings[0].part_of_speech, PartOfSpeech::Noun);
+    assert!(word.all_synonyms().any(|synonym| synonym == "distraction"));
+    let word = dict.get_lemma_definition("took after").await.unwrap();
+    assert_eq!(word.word, "take after");
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn looks_words_up_in_the_configured_language() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/es/ef%C3%ADmero",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let dict = Dictionary::builder()
+        .dictionary_url(format!("{}/api/v2", server.url))
+        .random_word_url(&server.url)
+        .config(test_config())
+        .language(Language::Spanish)
+        .build()
+        .unwrap();
+    let word = dict.get_lemma_definition("Efímero").await.unwrap();
+    dict.get_random_words(3, None).await.unwrap();
+
+    assert_eq!(word.language, Language::Spanish);
+    assert_eq!(word.level(), None);
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/api/v2/entries/es/ef%C3%ADmero");
+    assert_eq!(requests[1].query, "words=3&lang=es");
+}
+
+#[tokio::test]
+async fn attaches_translations_to_looked_up_words() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/ephemeral",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    let translations =
+        TranslationFile::parse("ephemeral|efímero, pasajero", Language::English, Language::Spanish).unwrap();
+    let dict = Dictionary::builder()
+        .dictionary_url(format!("{}/api/v2", server.url))
+        .config(test_config())
+        .translations(Arc::new(translations))
+        .build()
+        .unwrap();
+
+    let word = dict.get_definition("ephemeral").await.unwrap();
+    let phrase = dict.get_definition("red herring").await.unwrap();
+
+    assert_eq!(word.translations, ["efímero", "pasajero"]);
+    assert!(phrase.translations.is_empty());
+    assert_eq!(
+        dict.translations().map(|translations| translations.target_language()),
+        Some(Language::Spanish)
+    );
+    assert!(dictionary(&server, test_config()).translations().is_none());
+}
+
+#[test]
+fn rejects_translations_from_another_language() {
+    let translations = TranslationFile::parse("efímero|ephemeral", Language::Spanish, Language::English).unwrap();
+    let result = Dictionary::builder().translations(Arc::new(translations)).build();
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::UnsupportedLanguage(Language::English))
+    ));
+}
+
+#[test]
+fn rejects_languages_a_backend_does_not_support() {
+    let result = Dictionary::builder()
+        .random_word_backend(RandomWordBackend::FrequencyList)
+        .language(Language::German)
+        .build();
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::UnsupportedLanguage(Language::German))
+    ));
+    assert_eq!(Language::parse("DE"), Some(Language::German));
+    assert_eq!(Language::parse("spanish"), Some(Language::Spanish));
+}
+
+#[test]
+fn only_trusts_dictionaryapi_dev_with_english() {
+    assert_eq!(Dictionary::new().supported_languages(), [Language::English]);
+    assert!(matches!(
+        Dictionary::builder().language(Language::German).build(),
+        Err(DictionaryError::UnsupportedLanguage(Language::German))
+    ));
+
+    let self_hosted = Dictionary::builder()
+        .dictionary_url("http://localhost:8080/api/v2")
+        .language(Language::German)
+        .build()
+        .unwrap();
+    assert!(self_hosted.supported_languages().contains(&Language::German));
+
+    let subdomain = Dictionary::builder()
+        .dictionary_url("https://api.dictionaryapi.dev/api/v2")
+        .build()
+        .unwrap();
+    assert_eq!(subdomain.supported_languages(), [Language::English]);
+    // only the domain itself and its subdomains are dictionaryapi.dev
+    let lookalike = Dictionary::builder()
+        .dictionary_url("https://notdictionaryapi.dev/api/v2")
+        .language(Language::German)
+        .build()
+        .unwrap();
+    assert!(lookalike.supported_languages().contains(&Language::German));
+}
+
+#[tokio::test]
+async fn defines_inflected_words_by_their_lemma() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/goose",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    dictionary(&server, test_config())
+        .get_lemma_definition(" Geese")
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/api/v2/entries/en/goose");
+}
+
+#[tokio::test]
+async fn tries_guessed_lemmas_of_unknown_words() {
+    let server = MockServer::start().await;
+    for unknown in ["defenestrated", "defenestrat", "qwertyuiop"] {
+        server.route(
+            &format!("/api/v2/entries/en/{unknown}"),
+            [MockResponse::fixture(404, "not_found.json")],
+        );
+    }
+    server.route(
+        "/api/v2/entries/en/defenestrate",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    let dict = dictionary(&server, test_config());
+    dict.get_lemma_definition("defenestrated").await.unwrap();
+
+    let paths = server
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect::<Vec<String>>();
+    assert_eq!(paths.first().map(String::as_str), Some("/api/v2/entries/en/defenestrated"));
+    assert_eq!(paths.last().map(String::as_str), Some("/api/v2/entries/en/defenestrate"));
+    assert!(matches!(
+        dict.get_lemma_definition("qwertyuiop").await,
+        Err(DictionaryError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn forwards_random_word_options() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::new(200, r#"["lantern","whistle","cabinet"]"#)]);
+    let words = dictionary(&server, test_config())
+        .get_random_words(3, Some(7))
+        .await
+        .unwrap();
+
+    assert_eq!(words, ["lantern", "whistle", "cabinet"]);
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/api");
+    assert_eq!(request.query, "words=3&length=7");
+}
+
+#[tokio::test]
+async fn forwards_supported_query_filters_to_the_api() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let words = dictionary(&server, test_config())
+        .random_words(&RandomWordQuery::new(1).length(7).first_letter('L'))
+        .await
+        .unwrap();
+
+    // the mock ignores the filters, the words it returns are still checked
+    assert_eq!(words, ["lantern"]);
+    assert_eq!(server.requests()[0].query, "words=1&length=7&letter=l");
+}
+
+#[tokio::test]
+async fn filters_length_ranges_and_excluded_words_after_fetching() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let words = dictionary(&server, test_config())
+        .random_words(&RandomWordQuery::new(2).length_range(6..=7).exclude(["Lantern"]))
+        .await
+        .unwrap();
+
+    assert_eq!(words, ["pebble", "whistle"]);
+    // more words are asked for since some get filtered out
+    assert_eq!(server.requests()[0].query, "words=8");
+}
+
+#[tokio::test]
+async fn filters_random_words_by_part_of_speech() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    server.route(
+        "/api/v2/entries/en/lantern",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    server.route(
+        "/api/v2/entries/en/pebble",
+        [MockResponse::fixture(404, "not_found.json")],
+    );
+    server.route(
+        "/api/v2/entries/en/whistle",
+        [MockResponse::fixture(404, "not_found.json")],
+    );
+    let words = dictionary(&server, test_config())
+        .random_words(&RandomWordQuery::new(1).part_of_speech(PartOfSpeech::Adjective))
+        .await
+        .unwrap();
+
+    assert_eq!(words, ["lantern"]);
+}
+
+#[tokio::test]
+async fn fetches_more_random_words_to_replace_duplicates() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api",
+        [
+            MockResponse::new(200, r#"["lantern","Lantern","pebble"]"#),
+            MockResponse::fixture(200, "random_words.json"),
+        ],
+    );
+    let words = dictionary(&server, test_config())
+        .get_random_words(3, None)
+        .await
+        .unwrap();
+
+    assert_eq!(words, ["lantern", "pebble", "whistle"]);
+    let queries = server
+        .requests()
+        .into_iter()
+        .map(|request| request.query)
+        .collect::<Vec<String>>();
+    assert_eq!(queries, ["words=3", "words=1"]);
+}
+
+#[tokio::test]
+async fn gives_up_when_there_are_not_enough_random_words() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let result = dictionary(&server, test_config())
+        .get_random_words(4, None)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::NotEnoughWords {
+            requested: 4,
+            found: 3
+        })
+    ));
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn random_definitions_skip_words_the_dictionary_does_not_know() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    server.route(
+        "/api/v2/entries/en/lantern",
+        [MockResponse::fixture(404, "not_found.json")],
+    );
+    server.route(
+        "/api/v2/entries/en/pebble",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    let words = dictionary(&server, test_config())
+        .random_definitions(&RandomWordQuery::new(1))
+        .await
+        .unwrap();
+
+    assert_eq!(words.len(), 1);
+    assert_eq!(words[0].word, "ephemeral");
+}
+
+#[tokio::test]
+async fn frequency_list_backend_works_offline() {
+    let dict = Dictionary::builder()
+        .random_word_url("http://127.0.0.1:9")
+        .random_word_backend(RandomWordBackend::FrequencyList)
+        .build()
+        .unwrap();
+    let words = dict
+        .random_words(&RandomWordQuery::new(5).first_letter('s').length_range(4..=6))
+        .await
+        .unwrap();
+
+    assert_eq!(words.len(), 5);
+    for word in words {
+        assert!(word.starts_with('s'));
+        assert!((4..=6).contains(&word.len()));
+    }
+}
+
+#[tokio::test]
+async fn seeded_queries_pick_the_same_words_from_the_frequency_list() {
+    let dict = Dictionary::builder()
+        .random_word_url("http://127.0.0.1:9")
+        .random_word_backend(RandomWordBackend::FrequencyList)
+        .build()
+        .unwrap();
+    let query = RandomWordQuery::new(8).seed(42);
+    let words = dict.random_words(&query).await.unwrap();
+
+    assert_eq!(dict.random_words(&query).await.unwrap(), words);
+    assert_ne!(dict.random_words(&query.clone().seed(43)).await.unwrap(), words);
+}
+
+#[tokio::test]
+async fn sends_custom_headers_to_both_apis() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/ephemeral",
+        [MockResponse::fixture(200, "ephemeral.json")],
+    );
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let dict = Dictionary::builder()
+        .dictionary_url(format!("{}/api/v2/", server.url))
+        .random_word_url(&server.url)
+        .header(
+            HeaderName::from_static("x-api-key"),
+            HeaderValue::from_static("secret"),
+        )
+        .client(reqwest::Client::new())
+        .build()
+        .unwrap();
+    dict.get_definition("ephemeral").await.unwrap();
+    dict.get_random_word(None).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        assert_eq!(request.headers.get("x-api-key").map(String::as_str), Some("secret"));
+    }
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/ephemeral",
+        [
+            MockResponse::new(503, "unavailable"),
+            MockResponse::new(502, "bad gateway"),
+            MockResponse::fixture(200, "ephemeral.json"),
+        ],
+    );
+    let word = dictionary(&server, test_config())
+        .get_definition("ephemeral")
+        .await
+        .unwrap();
+
+    assert_eq!(word.word, "ephemeral");
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_last_retry() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::new(500, "broken")]);
+    let result = dictionary(&server, test_config())
+        .get_random_words(3, None)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::Status { status: 500, ref body }) if body == "broken"
+    ));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn waits_as_long_as_retry_after_asks() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api",
+        [
+            MockResponse::new(429, "slow down").header("Retry-After", "1"),
+            MockResponse::fixture(200, "random_words.json"),
+        ],
+    );
+    let config = HttpConfig {
+        max_backoff: Duration::from_secs(2),
+        ..test_config()
+    };
+    let start = Instant::now();
+    let words = dictionary(&server, config)
+        .get_random_words(3, None)
+        .await
+        .unwrap();
+
+    assert_eq!(words.len(), 3);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn gives_up_when_retry_after_exceeds_the_max_backoff() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api",
+        [
+            MockResponse::new(429, "slow down").header("Retry-After", "3600"),
+            MockResponse::fixture(200, "random_words.json"),
+        ],
+    );
+    let dict = dictionary(&server, test_config());
+    let start = Instant::now();
+    let result = dict.get_random_words(3, None).await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::RateLimited {
+            retry_after: Some(retry_after)
+        }) if retry_after == Duration::from_secs(3600)
+    ));
+    assert_eq!(server.requests().len(), 1);
+    // the other requests aren't held back by the hour the server asked for
+    dict.get_random_words(3, None).await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn times_out_slow_responses() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api",
+        [MockResponse::fixture(200, "random_words.json").delay(Duration::from_secs(5))],
+    );
+    let config = HttpConfig {
+        timeout: Duration::from_millis(200),
+        max_retries: 0,
+        ..test_config()
+    };
+    let start = Instant::now();
+    let result = dictionary(&server, config).get_random_words(3, None).await;
+
+    let error = result.unwrap_err();
+    assert!(matches!(error, DictionaryError::Timeout(_)));
+    assert!(error.source().is_some());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn spaces_out_requests() {
+    let server = MockServer::start().await;
+    server.route("/api", [MockResponse::fixture(200, "random_words.json")]);
+    let config = HttpConfig {
+        min_request_interval: Duration::from_millis(200),
+        ..test_config()
+    };
+    let dict = dictionary(&server, config);
+    let start = Instant::now();
+    let (first, second, third) = tokio::join!(
+        dict.get_random_word(None),
+        dict.get_random_word(None),
+        dict.get_random_word(None),
+    );
+
+    assert!(first.is_ok() && second.is_ok() && third.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn reports_rate_limiting_once_retries_run_out() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api",
+        [MockResponse::new(429, "slow down").header("Retry-After", "0")],
+    );
+    let result = dictionary(&server, test_config())
+        .get_random_words(3, None)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::RateLimited {
+            retry_after: Some(retry_after)
+        }) if retry_after.is_zero()
+    ));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn points_at_the_malformed_part_of_a_payload() {
+    let server = MockServer::start().await;
+    let payload = fixture("ephemeral.json").replace(
+        r#""definition":"Lasting for a short period of time.""#,
+        r#""definition":42"#,
+    );
+    server.route("/api/v2/entries/en/ephemeral", [MockResponse::new(200, payload)]);
+    let result = dictionary(&server, test_config())
+        .get_definition("ephemeral")
+        .await;
+
+    let Err(DictionaryError::MalformedPayload(error)) = result else {
+        panic!("expected a malformed payload, got {result:?}");
+    };
+    assert_eq!(error.path, "[0].meanings[1].definitions[0].definition");
+    assert!(error.source().is_some());
+}
+
+#[tokio::test]
+async fn reports_empty_responses() {
+    let server = MockServer::start().await;
+    server.route("/api/v2/entries/en/ephemeral", [MockResponse::new(200, "[]")]);
+    server.route("/api", [MockResponse::new(200, "[]")]);
+    let dict = dictionary(&server, test_config());
+
+    assert!(matches!(
+        dict.get_definition("ephemeral").await,
+        Err(DictionaryError::EmptyResponse)
+    ));
+    assert!(matches!(
+        dict.get_random_words(3, None).await,
+        Err(DictionaryError::EmptyResponse)
+    ));
+}
+
+#[tokio::test]
+async fn reports_unknown_parts_of_speech() {
+    let server = MockServer::start().await;
+    let payload = fixture("ephemeral.json").replace(r#""partOfSpeech":"noun""#, r#""partOfSpeech":"article""#);
+    server.route("/api/v2/entries/en/ephemeral", [MockResponse::new(200, payload)]);
+    let result = dictionary(&server, test_config())
+        .get_definition("ephemeral")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::UnknownPartOfSpeech { ref kind }) if kind == "article"
+    ));
+}
+
+#[tokio::test]
+async fn keeps_the_body_of_unexpected_statuses() {
+    let server = MockServer::start().await;
+    server.route(
+        "/api/v2/entries/en/ephemeral",
+        [MockResponse::new(403, r#"{"message":"forbidden"}"#)],
+    );
+    let result = dictionary(&server, test_config())
+        .get_definition("ephemeral")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DictionaryError::Status { status: 403, ref body }) if body.contains("forbidden")
+    ));
+    assert_eq!(server.requests().len(), 1);
+}
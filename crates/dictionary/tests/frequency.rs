@@ -0,0 +1,57 @@
+use dictionary::{frequency_rank, words_in_levels, Level};
+
+#[test]
+fn common_words_rank_higher_than_rare_ones() {
+    let the = frequency_rank("the").unwrap();
+    let time = frequency_rank("time").unwrap();
+    let government = frequency_rank("government").unwrap();
+    assert_eq!(the, 1);
+    assert!(the < time && time < government);
+    assert_eq!(frequency_rank("  The "), Some(1));
+    assert_eq!(frequency_rank("zymurgy"), None);
+}
+
+#[test]
+fn grouped_words_have_a_level_but_no_rank() {
+    // these were once ranked by their alphabetical position, `abandon` way above `yield`
+    for word in ["abandon", "yield"] {
+        assert_eq!(frequency_rank(word), None, "{word}");
+        assert_eq!(Level::of(word), Level::B2, "{word}");
+    }
+    assert_eq!(Level::of("accept"), Level::of("yell"));
+    assert_eq!(Level::of("ubiquitous"), Level::C1);
+    assert!(words_in_levels(Level::C1..=Level::C1).contains(&"ubiquitous"));
+}
+
+#[test]
+fn levels_follow_the_rank() {
+    assert_eq!(Level::of("the"), Level::A1);
+    assert_eq!(Level::of("zymurgy"), Level::C2);
+    assert_eq!(Level::from_rank(Some(1)), Level::A1);
+    assert_eq!(Level::from_rank(Some(800)), Level::A2);
+    assert_eq!(Level::from_rank(Some(801)), Level::B1);
+    assert_eq!(Level::from_rank(Some(100_000)), Level::B1);
+    assert_eq!(Level::of("international"), Level::B1);
+    assert_eq!(Level::from_rank(None), Level::C2);
+}
+
+#[test]
+fn level_neighborhood_stays_in_bounds() {
+    assert_eq!(Level::A1.neighborhood(), Level::A1..=Level::A2);
+    assert_eq!(Level::B1.neighborhood(), Level::A2..=Level::B2);
+    assert_eq!(Level::C2.neighborhood(), Level::C1..=Level::C2);
+}
+
+#[test]
+fn parses_levels() {
+    assert_eq!(Level::parse("b2"), Some(Level::B2));
+    assert_eq!(Level::parse("D1"), None);
+}
+
+#[test]
+fn lists_words_of_a_level() {
+    let words = words_in_levels(Level::A1..=Level::A1);
+    assert!(words.contains(&"the"));
+    assert!(words.iter().all(|word| Level::of(word) == Level::A1));
+    assert!(words_in_levels(Level::C2..=Level::C2).is_empty());
+}
@@ -0,0 +1,64 @@
+use dictionary::{inflect, inflections, lemma_candidates, lemmatize, normalize, same_lemma, Inflection};
+
+#[test]
+fn normalizes_case_and_whitespace() {
+    assert_eq!(normalize("  Ephemeral "), "ephemeral");
+    assert_eq!(normalize("Red \t Herring"), "red herring");
+}
+
+#[test]
+fn maps_inflected_forms_to_lemmas() {
+    assert_eq!(lemmatize("geese"), "goose");
+    assert_eq!(lemmatize("Running"), "run");
+    assert_eq!(lemmatize("went"), "go");
+    assert_eq!(lemmatize("stopped"), "stop");
+    assert_eq!(lemmatize("cities"), "city");
+    assert_eq!(lemmatize("happier"), "happy");
+    assert_eq!(lemmatize("glasses"), "glass");
+    assert_eq!(lemmatize("making"), "make");
+}
+
+#[test]
+fn keeps_words_that_are_lemmas_themselves() {
+    // `flower` isn't the comparative of `flow`
+    assert_eq!(lemmatize("flower"), "flower");
+    assert_eq!(lemmatize("glass"), "glass");
+    assert_eq!(lemmatize("zymurgy"), "zymurgy");
+}
+
+#[test]
+fn guesses_lemmas_of_unknown_words_from_the_spelling() {
+    let candidates = lemma_candidates("defenestrated");
+    assert!(candidates.contains(&"defenestrate".to_owned()));
+    assert!(!candidates.contains(&"defenestrated".to_owned()));
+    assert!(lemma_candidates("take after").is_empty());
+}
+
+#[test]
+fn generates_inflections() {
+    assert_eq!(inflect("goose", Inflection::Plural), "geese");
+    assert_eq!(inflect("box", Inflection::Plural), "boxes");
+    assert_eq!(inflect("city", Inflection::Plural), "cities");
+    assert_eq!(inflect("stop", Inflection::Past), "stopped");
+    assert_eq!(inflect("study", Inflection::Past), "studied");
+    assert_eq!(inflect("bake", Inflection::Past), "baked");
+    assert_eq!(inflect("run", Inflection::PresentParticiple), "running");
+    assert_eq!(inflect("make", Inflection::PresentParticiple), "making");
+    assert_eq!(inflect("lie", Inflection::PresentParticiple), "lying");
+    assert_eq!(inflect("see", Inflection::PresentParticiple), "seeing");
+    assert_eq!(inflect("big", Inflection::Comparative), "bigger");
+    assert_eq!(inflect("happy", Inflection::Superlative), "happiest");
+    assert_eq!(inflect("open", Inflection::PresentParticiple), "opening");
+    assert_eq!(inflect("take after", Inflection::Past), "took after");
+    assert!(inflections("walk").contains(&"walked".to_owned()));
+}
+
+#[test]
+fn compares_words_by_lemma() {
+    assert!(same_lemma("Ephemeral", "ephemeral"));
+    assert!(same_lemma("geese", "goose"));
+    assert!(same_lemma("ran", "running"));
+    assert!(same_lemma("defenestrate", "defenestrated"));
+    assert!(!same_lemma("flower", "flow"));
+    assert!(!same_lemma("goose", "moose"));
+}
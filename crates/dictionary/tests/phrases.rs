@@ -0,0 +1,20 @@
+use dictionary::{inflect, is_phrase, lemmatize, phrases, same_lemma, Inflection};
+
+#[test]
+fn bundled_phrases_are_lemmas() {
+    let phrases = phrases();
+    assert!(phrases.contains(&"take after"));
+    for phrase in phrases {
+        assert!(is_phrase(phrase), "'{phrase}' is a single word");
+        assert_eq!(lemmatize(phrase), phrase);
+    }
+}
+
+#[test]
+fn inflected_phrases_map_to_their_lemma() {
+    assert_eq!(lemmatize("Took  After"), "take after");
+    assert_eq!(lemmatize("giving up"), "give up");
+    assert_eq!(inflect("put off", Inflection::PresentParticiple), "putting off");
+    assert!(same_lemma("spilled the beans", "spill the beans"));
+    assert!(!is_phrase(" ephemeral "));
+}
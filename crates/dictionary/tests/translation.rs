@@ -0,0 +1,39 @@
+use std::io::ErrorKind;
+
+use dictionary::{Language, TranslationBackend, TranslationFile};
+
+const TRANSLATIONS: &str = "
+# english to spanish
+ephemeral|efímero, pasajero
+fleeting | Pasajero
+red herring|pista falsa
+
+ephemeral|fugaz
+";
+
+fn translations() -> TranslationFile {
+    TranslationFile::parse(TRANSLATIONS, Language::English, Language::Spanish).unwrap()
+}
+
+#[test]
+fn translates_words_both_ways() {
+    let translations = translations();
+    assert_eq!(translations.len(), 3);
+    assert_eq!(translations.translate(" Ephemeral "), ["efímero", "pasajero", "fugaz"]);
+    assert_eq!(translations.translate("red  herring"), ["pista falsa"]);
+    assert!(translations.translate("zymurgy").is_empty());
+    assert_eq!(translations.words_translated_as("PASAJERO"), ["ephemeral", "fleeting"]);
+    assert_eq!(
+        translations.all_translations(),
+        ["efímero", "fugaz", "pasajero", "pista falsa"]
+    );
+    assert_eq!(translations.target_language(), Language::Spanish);
+}
+
+#[test]
+fn rejects_lines_without_translations() {
+    for contents in ["ephemeral", "ephemeral|", "|efímero"] {
+        let error = TranslationFile::parse(contents, Language::English, Language::Spanish).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData, "{contents}");
+    }
+}
@@ -0,0 +1,2 @@
+DROP TABLE "deck_words";
+DROP TABLE "decks";
\ No newline at end of file
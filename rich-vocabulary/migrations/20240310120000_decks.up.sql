@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS "decks"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "name" VARCHAR NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS "deck_words"(
+    "deck_uid" INTEGER NOT NULL REFERENCES "decks"("uid") ON DELETE CASCADE,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    PRIMARY KEY ("deck_uid", "word_uid")
+);
@@ -0,0 +1 @@
+-- Merged duplicates can't be split back apart, there is nothing to undo
@@ -0,0 +1,29 @@
+-- Every group of duplicates is merged into its oldest entry, words are duplicates when they only differ by case
+-- or surrounding whitespace. `SqliteStorage::repair` merges with the same LOWER(TRIM("word")) key.
+CREATE TEMPORARY TABLE "word_merges" AS
+    SELECT "duplicate"."uid" AS "duplicate_uid", "kept"."uid" AS "kept_uid"
+    FROM "words" AS "duplicate"
+    JOIN (SELECT MIN("uid") AS "uid", LOWER(TRIM("word")) AS "key" FROM "words" GROUP BY LOWER(TRIM("word"))) AS "kept"
+        ON LOWER(TRIM("duplicate"."word")) = "kept"."key" AND "duplicate"."uid" != "kept"."uid";
+
+UPDATE "words" SET
+    "score" = (
+        SELECT ROUND(AVG("group_words"."score")) FROM "words" AS "group_words"
+        WHERE LOWER(TRIM("group_words"."word")) = LOWER(TRIM("words"."word"))
+    ),
+    "last_quizzed" = (
+        SELECT MAX("group_words"."last_quizzed") FROM "words" AS "group_words"
+        WHERE LOWER(TRIM("group_words"."word")) = LOWER(TRIM("words"."word"))
+    )
+WHERE "uid" IN (SELECT "kept_uid" FROM "word_merges");
+
+INSERT OR IGNORE INTO "deck_words"("deck_uid", "word_uid")
+    SELECT "deck_words"."deck_uid", "word_merges"."kept_uid" FROM "deck_words"
+    JOIN "word_merges" ON "deck_words"."word_uid" = "word_merges"."duplicate_uid";
+
+DELETE FROM "words" WHERE "uid" IN (SELECT "duplicate_uid" FROM "word_merges");
+
+DROP TABLE "word_merges";
+
+-- the unique index built next only ignores case, so the kept words lose their surrounding whitespace
+UPDATE "words" SET "word" = TRIM("word") WHERE "word" != TRIM("word");
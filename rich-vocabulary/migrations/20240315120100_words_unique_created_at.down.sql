@@ -0,0 +1,21 @@
+CREATE TEMPORARY TABLE "deck_words_backup" AS SELECT "deck_uid", "word_uid" FROM "deck_words";
+DROP TABLE "deck_words";
+
+CREATE TABLE "words_old"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "word" VARCHAR NOT NULL,
+    "last_quizzed" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "score" INTEGER NOT NULL
+);
+INSERT INTO "words_old"("uid", "word", "last_quizzed", "score")
+    SELECT "uid", "word", "last_quizzed", "score" FROM "words";
+DROP TABLE "words";
+ALTER TABLE "words_old" RENAME TO "words";
+
+CREATE TABLE "deck_words"(
+    "deck_uid" INTEGER NOT NULL REFERENCES "decks"("uid") ON DELETE CASCADE,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    PRIMARY KEY ("deck_uid", "word_uid")
+);
+INSERT INTO "deck_words"("deck_uid", "word_uid") SELECT "deck_uid", "word_uid" FROM "deck_words_backup";
+DROP TABLE "deck_words_backup";
@@ -0,0 +1,26 @@
+-- SQLite can't add constraints or a CURRENT_TIMESTAMP default to an existing table, so it is rebuilt.
+-- The deck links are set aside first because dropping "words" would cascade into them.
+-- When words were saved before wasn't recorded so far, their "created_at" stays unknown.
+CREATE TEMPORARY TABLE "deck_words_backup" AS SELECT "deck_uid", "word_uid" FROM "deck_words";
+DROP TABLE "deck_words";
+
+CREATE TABLE "words_new"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "word" VARCHAR NOT NULL COLLATE NOCASE,
+    "last_quizzed" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "score" INTEGER NOT NULL,
+    "created_at" DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+INSERT INTO "words_new"("uid", "word", "last_quizzed", "score", "created_at")
+    SELECT "uid", "word", "last_quizzed", "score", NULL FROM "words";
+DROP TABLE "words";
+ALTER TABLE "words_new" RENAME TO "words";
+CREATE UNIQUE INDEX "words_word_unique" ON "words"("word" COLLATE NOCASE);
+
+CREATE TABLE "deck_words"(
+    "deck_uid" INTEGER NOT NULL REFERENCES "decks"("uid") ON DELETE CASCADE,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    PRIMARY KEY ("deck_uid", "word_uid")
+);
+INSERT INTO "deck_words"("deck_uid", "word_uid") SELECT "deck_uid", "word_uid" FROM "deck_words_backup";
+DROP TABLE "deck_words_backup";
@@ -0,0 +1 @@
+DROP TABLE "word_parts_of_speech";
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS "word_parts_of_speech"(
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    "part_of_speech" VARCHAR NOT NULL,
+    PRIMARY KEY ("word_uid", "part_of_speech")
+);
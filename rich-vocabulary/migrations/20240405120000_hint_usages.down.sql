@@ -0,0 +1 @@
+DROP TABLE "hint_usages";
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS "hint_usages"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    "hint" VARCHAR NOT NULL,
+    "used_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
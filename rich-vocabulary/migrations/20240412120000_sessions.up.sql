@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS "sessions"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "started_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS "answers"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "session_uid" INTEGER NOT NULL REFERENCES "sessions"("uid") ON DELETE CASCADE,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    "question_kind" VARCHAR NOT NULL,
+    "correct" BOOLEAN NOT NULL,
+    "answered_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1 @@
+ALTER TABLE "answers" DROP COLUMN "latency_ms";
\ No newline at end of file
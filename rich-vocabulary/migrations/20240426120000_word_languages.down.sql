@@ -0,0 +1,5 @@
+-- Fails while the same spelling is saved in several languages, since only one of the words could be kept.
+-- Removing the words of the other languages first lets it run.
+DROP INDEX "words_language_word_unique";
+CREATE UNIQUE INDEX "words_word_unique" ON "words"("word" COLLATE NOCASE);
+ALTER TABLE "words" DROP COLUMN "language";
@@ -0,0 +1,5 @@
+-- Every saved word belongs to a language, the words saved so far are english.
+-- The same spelling can be saved once per language.
+ALTER TABLE "words" ADD COLUMN "language" VARCHAR NOT NULL DEFAULT 'en';
+DROP INDEX "words_word_unique";
+CREATE UNIQUE INDEX "words_language_word_unique" ON "words"("language", "word" COLLATE NOCASE);
@@ -0,0 +1,4 @@
+-- Fails while the same name is used by decks of several languages
+DROP INDEX "decks_language_name_unique";
+CREATE UNIQUE INDEX "decks_name_unique" ON "decks"("name");
+ALTER TABLE "decks" DROP COLUMN "language";
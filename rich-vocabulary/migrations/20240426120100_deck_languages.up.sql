@@ -0,0 +1,30 @@
+-- Decks belong to a language like the words in them, a deck takes the language most of its words have.
+-- The same name can be used once per language.
+-- deck_words is rebuilt as well, dropping decks while it references them would empty it.
+CREATE TABLE "deck_words_backup" AS SELECT "deck_uid", "word_uid" FROM "deck_words";
+DROP TABLE "deck_words";
+CREATE TABLE "decks_backup" AS SELECT "uid", "name" FROM "decks";
+DROP TABLE "decks";
+
+CREATE TABLE "decks"(
+    "uid" INTEGER PRIMARY KEY NOT NULL,
+    "name" VARCHAR NOT NULL,
+    "language" VARCHAR NOT NULL DEFAULT 'en'
+);
+CREATE UNIQUE INDEX "decks_language_name_unique" ON "decks"("language", "name");
+INSERT INTO "decks"("uid", "name", "language")
+    SELECT "uid", "name", COALESCE((
+        SELECT "words"."language" FROM "deck_words_backup"
+        JOIN "words" ON "words"."uid" = "deck_words_backup"."word_uid"
+        WHERE "deck_words_backup"."deck_uid" = "decks_backup"."uid"
+        GROUP BY "words"."language" ORDER BY COUNT(*) DESC, "words"."language" LIMIT 1
+    ), 'en') FROM "decks_backup";
+DROP TABLE "decks_backup";
+
+CREATE TABLE "deck_words"(
+    "deck_uid" INTEGER NOT NULL REFERENCES "decks"("uid") ON DELETE CASCADE,
+    "word_uid" INTEGER NOT NULL REFERENCES "words"("uid") ON DELETE CASCADE,
+    PRIMARY KEY ("deck_uid", "word_uid")
+);
+INSERT INTO "deck_words"("deck_uid", "word_uid") SELECT "deck_uid", "word_uid" FROM "deck_words_backup";
+DROP TABLE "deck_words_backup";
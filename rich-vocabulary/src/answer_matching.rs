@@ -0,0 +1,176 @@
+use crate::questions::Answer;
+
+/// How similar a typed answer has to be to an option to be accepted
+const FUZZY_THRESHOLD: f64 = 0.88;
+/// How much closer the best option has to be than the second best
+const FUZZY_MARGIN: f64 = 0.08;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Skip,
+    /// Asks for a hint by name, the name may be empty when none was given
+    Hint(String),
+    /// Looks up a word without leaving the question, the word may be empty when none was given
+    Define(String),
+    Quit,
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerInput {
+    /// The index of the chosen answer
+    Choice(usize),
+    Command(Command),
+    /// The input could refer to every one of these answers
+    Ambiguous(Vec<usize>),
+    Unknown,
+}
+
+/// Figures out what the user meant by their input.
+/// Answers can be chosen by their number, their letter, their full text, another form of the word,
+/// the start of their text or a text close enough to only one of them.
+pub fn match_answer(input: &str, answers: &[Answer]) -> AnswerInput {
+    let input = input.trim();
+    if let Some(command) = input.strip_prefix(':') {
+        return parse_command(command);
+    }
+    if input.is_empty() {
+        return AnswerInput::Unknown;
+    }
+    if let Ok(number) = input.parse::<usize>() {
+        return match number.checked_sub(1) {
+            Some(index) if index < answers.len() => AnswerInput::Choice(index),
+            _ => AnswerInput::Unknown,
+        };
+    }
+    let input = input.to_lowercase();
+    if let Some(index) = letter_index(&input) {
+        if index < answers.len() {
+            return AnswerInput::Choice(index);
+        }
+    }
+    let contents = answers
+        .iter()
+        .map(|answer| answer.content.trim().to_lowercase())
+        .collect::<Vec<String>>();
+    if let Some(index) = contents.iter().position(|content| *content == input) {
+        return AnswerInput::Choice(index);
+    }
+    // `geese` chooses `goose`, but only when no other answer has the same lemma
+    let same_lemma = contents
+        .iter()
+        .enumerate()
+        .filter(|(_, content)| dictionary::same_lemma(content, &input))
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+    if let [index] = same_lemma[..] {
+        return AnswerInput::Choice(index);
+    }
+    let prefixed = contents
+        .iter()
+        .enumerate()
+        .filter(|(_, content)| content.starts_with(&input))
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+    match prefixed[..] {
+        [index] => return AnswerInput::Choice(index),
+        [_, _, ..] => return AnswerInput::Ambiguous(prefixed),
+        [] => {}
+    }
+    fuzzy_match(&input, &contents)
+}
+
+fn parse_command(command: &str) -> AnswerInput {
+    let (name, argument) = command
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((command.trim(), ""));
+    let command = match &name.to_lowercase()[..] {
+        "s" | "skip" => Command::Skip,
+        "h" | "hint" => Command::Hint(argument.trim().to_owned()),
+        "d" | "define" => Command::Define(argument.trim().to_owned()),
+        "q" | "quit" | "exit" => Command::Quit,
+        "?" | "help" => Command::Help,
+        _ => return AnswerInput::Unknown,
+    };
+    AnswerInput::Command(command)
+}
+
+/// Maps `a`, `b`, `c`... to 0, 1, 2...
+fn letter_index(input: &str) -> Option<usize> {
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter @ 'a'..='z'), None) => Some(letter as usize - 'a' as usize),
+        _ => None,
+    }
+}
+
+fn fuzzy_match(input: &str, contents: &[String]) -> AnswerInput {
+    let mut similarities = contents
+        .iter()
+        .map(|content| strsim::jaro_winkler(content, input))
+        .enumerate()
+        .collect::<Vec<(usize, f64)>>();
+    // most similar at the start, equally similar ones keep the order of the answers
+    similarities.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    let close = similarities
+        .iter()
+        .filter(|(_, similarity)| *similarity >= FUZZY_THRESHOLD)
+        .collect::<Vec<&(usize, f64)>>();
+    match close[..] {
+        [] => AnswerInput::Unknown,
+        [(index, _)] => AnswerInput::Choice(*index),
+        [(index, best), (_, second), ..] if best - second > FUZZY_MARGIN => {
+            AnswerInput::Choice(*index)
+        }
+        _ => AnswerInput::Ambiguous(close.iter().map(|(index, _)| *index).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(contents: &[&str]) -> Vec<Answer> {
+        contents
+            .iter()
+            .map(|content| Answer {
+                content: (*content).to_owned(),
+                correct: false,
+                word_uid: None,
+                word: (*content).to_owned(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_every_way_of_choosing_an_answer() {
+        let answers = answers(&["goose", "Receive", "deceive", "banana split"]);
+        let cases = [
+            ("b", AnswerInput::Choice(1)),
+            ("2", AnswerInput::Choice(1)),
+            (" D ", AnswerInput::Choice(3)),
+            // out of range, and no answer starts with it
+            ("e", AnswerInput::Unknown),
+            ("5", AnswerInput::Unknown),
+            ("0", AnswerInput::Unknown),
+            // out of range as a letter, but the start of an answer
+            ("g", AnswerInput::Choice(0)),
+            ("receive", AnswerInput::Choice(1)),
+            ("  BANANA Split ", AnswerInput::Choice(3)),
+            ("ban", AnswerInput::Choice(3)),
+            ("dec", AnswerInput::Choice(2)),
+            ("geese", AnswerInput::Choice(0)),
+            ("banana splt", AnswerInput::Choice(3)),
+            // as close to both, the margin can't tell them apart
+            ("xeceive", AnswerInput::Ambiguous(vec![1, 2])),
+            ("zymurgy", AnswerInput::Unknown),
+            ("", AnswerInput::Unknown),
+            (":skip", AnswerInput::Command(Command::Skip)),
+            (":h example", AnswerInput::Command(Command::Hint("example".to_owned()))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(match_answer(input, &answers), expected, "input {input:?}");
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use chrono::{Datelike, NaiveDate};
+use dictionary::{words_in_levels, Level};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::storage::WordEntry;
+
+/// The level assumed for someone who hasn't saved any words yet
+const DEFAULT_LEVEL: Level = Level::B1;
+/// The word of the day is an advanced but not obscure word
+const WORD_OF_THE_DAY_LEVELS: RangeInclusive<Level> = Level::B2..=Level::C1;
+
+/// Guesses the level of the user from their saved words, it is the level most of them have
+pub fn estimate_level(saved: &[WordEntry]) -> Level {
+    Level::ALL
+        .into_iter()
+        .map(|level| {
+            let count = saved
+                .iter()
+                .filter(|entry| Level::of(&entry.word) == level)
+                .count();
+            (level, count)
+        })
+        .filter(|(_, count)| *count > 0)
+        // on a tie the more advanced level wins
+        .max_by_key(|(_, count)| *count)
+        .map_or(DEFAULT_LEVEL, |(level, _)| level)
+}
+
+/// Words of the frequency list at the given level that aren't saved yet, in a random order
+pub fn candidates(
+    level: Level,
+    length: Option<usize>,
+    saved: &HashSet<String>,
+    rng: &mut impl Rng,
+) -> Vec<&'static str> {
+    let mut words = words_in_levels(level..=level)
+        .into_iter()
+        .filter(|word| length.is_none_or(|length| word.chars().count() == length))
+        .filter(|word| !saved.contains(*word))
+        .collect::<Vec<&str>>();
+    words.shuffle(rng);
+    words
+}
+
+/// The words the word of the day is picked from in the order they should be tried,
+/// the first one that can be defined is the word of the day. The order only depends on the date.
+pub fn word_of_the_day_candidates(date: NaiveDate) -> Vec<&'static str> {
+    let mut words = words_in_levels(WORD_OF_THE_DAY_LEVELS);
+    // chacha keeps the word of a day the same on every platform and rand version
+    let mut rng = ChaCha8Rng::seed_from_u64(date.num_days_from_ce().unsigned_abs().into());
+    words.shuffle(&mut rng);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(words: &[&str]) -> Vec<WordEntry> {
+        words
+            .iter()
+            .enumerate()
+            .map(|(uid, word)| WordEntry {
+                uid: uid as i64,
+                word: (*word).to_owned(),
+                last_quizzed: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().into(),
+                score: 500,
+                created_at: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn estimates_the_level_most_saved_words_have() {
+        assert_eq!(estimate_level(&[]), DEFAULT_LEVEL);
+        assert_eq!(estimate_level(&saved(&["the", "abandon", "yield"])), Level::B2);
+        assert_eq!(estimate_level(&saved(&["the", "of", "ubiquitous"])), Level::A1);
+        // on a tie the more advanced level wins
+        assert_eq!(estimate_level(&saved(&["the", "ubiquitous"])), Level::C1);
+    }
+
+    #[test]
+    fn the_word_of_the_day_only_changes_with_the_day() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        assert_eq!(word_of_the_day_candidates(day)[0], word_of_the_day_candidates(day)[0]);
+        let words_of_the_week = day
+            .iter_days()
+            .take(7)
+            .map(|day| word_of_the_day_candidates(day)[0])
+            .collect::<HashSet<&str>>();
+        assert!(words_of_the_week.len() > 1, "{words_of_the_week:?}");
+        assert!(words_of_the_week
+            .iter()
+            .all(|word| WORD_OF_THE_DAY_LEVELS.contains(&Level::of(word))));
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::RangeInclusive;
+
+use dictionary::{Level, PartOfSpeech};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::storage::WordFilter;
+
+/// How strongly similar candidates are preferred at the highest difficulty
+const SIMILARITY_PREFERENCE: f64 = 10.0;
+/// From this difficulty on random words are asked to have the length of the right answer
+const MATCH_LENGTH_DIFFICULTY: f64 = 0.7;
+/// Scores range from 0 (known) to 1000 (unknown), new words start at 500
+const MAX_SCORE: f64 = 1000.0;
+
+/// How hard the wrong answers should be to tell apart from the right one, between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty(f64);
+
+impl Difficulty {
+    /// The better a word is known the harder its questions get
+    pub fn from_score(score: i64) -> Self {
+        Self((1.0 - score as f64 / MAX_SCORE).clamp(0.0, 1.0))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    pub fn matches_length(&self) -> bool {
+        self.0 >= MATCH_LENGTH_DIFFICULTY
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self(0.5)
+    }
+}
+
+/// Decides which wrong answers a question gets
+#[derive(Debug, Clone, Default)]
+pub struct DistractorOptions {
+    /// Which saved words may be used
+    pub filter: WordFilter,
+    pub difficulty: Difficulty,
+    /// Distractors are never more advanced than this level
+    pub max_level: Option<Level>,
+}
+
+impl DistractorOptions {
+    /// Only allows distractors used as the given part of speech
+    pub fn with_part_of_speech(&self, part_of_speech: PartOfSpeech) -> Self {
+        Self {
+            filter: WordFilter {
+                part_of_speech: Some(part_of_speech),
+                ..self.filter.clone()
+            },
+            difficulty: self.difficulty,
+            max_level: self.max_level,
+        }
+    }
+
+    pub fn allows_level(&self, level: Level) -> bool {
+        self.max_level.is_none_or(|max_level| level <= max_level)
+    }
+
+    /// The levels random distractors for the reference should be taken from, words of a similar frequency
+    /// are harder to rule out. None when any random word will do, which is the case for rare words without a level limit.
+    pub fn random_word_levels(&self, reference: &str) -> Option<RangeInclusive<Level>> {
+        let level = Level::of(reference);
+        if level == Level::C2 && self.max_level.is_none() {
+            return None;
+        }
+        let max_level = self.max_level.unwrap_or(Level::C2);
+        let neighborhood = level.neighborhood();
+        Some((*neighborhood.start()).min(max_level)..=(*neighborhood.end()).min(max_level))
+    }
+}
+
+/// How easily one word can be mistaken for another, between 0 and 1
+pub fn similarity(candidate: &str, reference: &str) -> f64 {
+    let candidate = candidate.to_lowercase();
+    let reference = reference.to_lowercase();
+    let lengths = (candidate.chars().count(), reference.chars().count());
+    let length_similarity = match lengths.0.max(lengths.1) {
+        0 => 1.0,
+        longer => lengths.0.min(lengths.1) as f64 / longer as f64,
+    };
+    let level_distance = (Level::of(&candidate) as i32 - Level::of(&reference) as i32).abs();
+    let level_similarity = 1.0 - level_distance as f64 / (Level::ALL.len() - 1) as f64;
+    0.5 * strsim::jaro_winkler(&candidate, &reference) + 0.3 * length_similarity + 0.2 * level_similarity
+}
+
+/// Picks up to `count` candidates, the higher the difficulty the more the ones similar to the reference are preferred.
+/// At the lowest difficulty every candidate is as likely to be picked.
+pub fn pick<'a, T>(
+    candidates: &'a [T],
+    word: impl Fn(&T) -> &str,
+    reference: &str,
+    count: usize,
+    difficulty: Difficulty,
+    rng: &mut impl Rng,
+) -> Vec<&'a T> {
+    let preference = SIMILARITY_PREFERENCE * difficulty.value();
+    candidates
+        .choose_multiple_weighted(rng, count, |candidate| {
+            (preference * similarity(word(candidate), reference)).exp()
+        })
+        // the weights are always positive and finite
+        .map(|picked| picked.collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    const CANDIDATES: [&str; 5] = ["deceive", "banana", "elephant", "yesterday", "quickly"];
+
+    #[test]
+    fn scores_map_to_difficulties() {
+        let cases = [(0, 1.0), (250, 0.75), (500, 0.5), (1000, 0.0), (-50, 1.0), (1200, 0.0)];
+        for (score, expected) in cases {
+            assert_eq!(Difficulty::from_score(score), Difficulty(expected), "score {score}");
+        }
+        assert!(Difficulty::from_score(250).matches_length());
+        assert!(!Difficulty::from_score(350).matches_length());
+    }
+
+    #[test]
+    fn picks_at_most_count_different_candidates() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        for count in 0..=CANDIDATES.len() + 2 {
+            let mut picked = pick(&CANDIDATES, |word| word, "receive", count, Difficulty::default(), &mut rng);
+            assert_eq!(picked.len(), count.min(CANDIDATES.len()));
+            picked.sort();
+            picked.dedup();
+            assert_eq!(picked.len(), count.min(CANDIDATES.len()));
+        }
+    }
+
+    #[test]
+    fn harder_questions_prefer_similar_candidates() {
+        let similar_picks = |difficulty| {
+            let mut rng = ChaCha8Rng::seed_from_u64(7);
+            (0..500)
+                .filter(|_| pick(&CANDIDATES, |word| word, "receive", 1, difficulty, &mut rng)[0] == &"deceive")
+                .count()
+        };
+        let easy = similar_picks(Difficulty::from_score(1000));
+        let hard = similar_picks(Difficulty::from_score(0));
+        assert!(easy < 150, "picked the similar word {easy} times when easy");
+        assert!(hard > 2 * easy, "picked the similar word {hard} times when hard, {easy} when easy");
+    }
+}
@@ -0,0 +1,145 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::questions::Question;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    FirstLetter,
+    PartOfSpeech,
+    Example,
+    /// Rules out two of the wrong answers
+    FiftyFifty,
+}
+
+impl Hint {
+    pub const ALL: [Hint; 4] = [
+        Hint::FirstLetter,
+        Hint::PartOfSpeech,
+        Hint::Example,
+        Hint::FiftyFifty,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match &name.trim().to_lowercase()[..] {
+            "letter" | "first" | "l" => Some(Hint::FirstLetter),
+            "pos" | "part" | "p" => Some(Hint::PartOfSpeech),
+            "example" | "e" => Some(Hint::Example),
+            "50" | "50/50" | "fifty" => Some(Hint::FiftyFifty),
+            _ => None,
+        }
+    }
+
+    /// The name the hint is stored with
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hint::FirstLetter => "first_letter",
+            Hint::PartOfSpeech => "part_of_speech",
+            Hint::Example => "example",
+            Hint::FiftyFifty => "fifty_fifty",
+        }
+    }
+
+    pub fn usage(&self) -> &'static str {
+        match self {
+            Hint::FirstLetter => ":hint letter   reveal the first letter of the answer",
+            Hint::PartOfSpeech => ":hint pos      show the part of speech of the word",
+            Hint::Example => ":hint example  show an example sentence",
+            Hint::FiftyFifty => ":hint 50       rule out two wrong answers",
+        }
+    }
+
+    /// How much of the reward for a correct answer the hint takes away
+    fn cost(&self) -> f64 {
+        match self {
+            Hint::FirstLetter => 0.25,
+            Hint::PartOfSpeech => 0.15,
+            Hint::Example => 0.25,
+            Hint::FiftyFifty => 0.5,
+        }
+    }
+}
+
+/// Keeps track of the hints used on a single question
+#[derive(Debug, Default)]
+pub struct HintState {
+    used: Vec<Hint>,
+}
+
+impl HintState {
+    pub fn used(&self) -> &[Hint] {
+        &self.used
+    }
+
+    /// Reveals a hint, returns None if the hint was already used or isn't available for the question
+    pub fn reveal(&mut self, hint: Hint, question: &Question, rng: &mut impl Rng) -> Option<String> {
+        if self.used.contains(&hint) {
+            return None;
+        }
+        let message = match hint {
+            Hint::FirstLetter => {
+                let answer = question.answers.iter().find(|answer| answer.correct)?;
+                let letter = answer.content.chars().next()?;
+                format!("The answer starts with '{letter}'.")
+            }
+            Hint::PartOfSpeech => {
+                let part_of_speech = question.hints.part_of_speech.as_ref()?;
+                format!("The word is used as a {}.", part_of_speech.as_str())
+            }
+            Hint::Example => {
+                let example = question.hints.example.as_ref()?;
+                format!("Example: {example}")
+            }
+            Hint::FiftyFifty => {
+                let wrong_answers = question
+                    .answers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, answer)| !answer.correct)
+                    .map(|(index, _)| index)
+                    .collect::<Vec<usize>>();
+                let mut eliminated = wrong_answers
+                    .choose_multiple(rng, 2)
+                    .map(|index| format!("[{}]", index + 1))
+                    .collect::<Vec<String>>();
+                if eliminated.is_empty() {
+                    return None;
+                }
+                eliminated.sort();
+                format!("These are not the answer: {}.", eliminated.join(", "))
+            }
+        };
+        self.used.push(hint);
+        Some(message)
+    }
+
+    /// Scales the score modifier of a correct answer down by the hints that were used,
+    /// using every hint leaves the score as it is
+    pub fn reward(&self, modifier: f64) -> f64 {
+        let cost = self.used.iter().map(Hint::cost).sum::<f64>().min(1.0);
+        1.0 - (1.0 - modifier) * (1.0 - cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_take_away_from_the_reward() {
+        let cases: [(&[Hint], f64); 7] = [
+            (&[], 0.8),
+            (&[Hint::FirstLetter], 0.85),
+            (&[Hint::PartOfSpeech], 0.83),
+            (&[Hint::Example], 0.85),
+            (&[Hint::FiftyFifty], 0.9),
+            (&[Hint::FirstLetter, Hint::PartOfSpeech], 0.88),
+            // the costs add up to more than the whole reward, the score is left as it is
+            (&Hint::ALL, 1.0),
+        ];
+        for (used, expected) in cases {
+            let state = HintState { used: used.to_vec() };
+            let reward = state.reward(0.8);
+            assert!((reward - expected).abs() < 1e-9, "hints {used:?} gave {reward}");
+        }
+    }
+}
@@ -1,22 +1,97 @@
-use dictionary::{Dictionary, Word};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use dictionary::{
+    Dictionary, DictionaryError, Language, Level, PartOfSpeech, RandomWordQuery, TranslationBackend, TranslationFile, Word,
+};
+use futures::{stream, StreamExt};
 use questions::{
-    generate_question_definition_word, generate_question_word_definition, generate_question_word_synonym, Question, QuestionGenerationError
+    generate_question_definition_word, generate_question_translation_word, generate_question_word_definition,
+    generate_question_word_synonym, generate_question_word_translation, Question, QuestionGenerationError, QuestionKind,
 };
-use storage::Storage;
-use utilities::{input, str_to_bool};
+use storage::{DeckEntry, SessionRange, SqliteStorage, Storage, StorageResult, WordEntry, WordFilter};
+use utilities::{input, input_async, input_timeout, split_arguments, str_to_bool};
 
-use rand::{seq::SliceRandom, Rng};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-use crate::questions::Answer;
+use crate::answer_matching::{match_answer, AnswerInput, Command};
+use crate::distractors::{Difficulty, DistractorOptions};
+use crate::hints::{Hint, HintState};
+use crate::summary::{SessionRecorder, WordOutcome};
+use crate::timing::{Countdown, TimeLimits};
+mod answer_matching;
+mod discovery;
+mod distractors;
+mod hints;
 mod questions;
 mod storage;
+mod suggestions;
+mod summary;
+mod timing;
 mod utilities;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let storage = Storage::initialize().await?;
+    let args = std::env::args().collect::<Vec<String>>();
+    let storage = if args.iter().any(|arg| arg == "--in-memory") {
+        println!("Running with an in memory database, nothing will be saved.");
+        SqliteStorage::in_memory().await?
+    } else {
+        SqliteStorage::initialize().await?
+    };
+    let language = match args.iter().position(|arg| arg == "--language") {
+        Some(index) => match args.get(index + 1).and_then(|language| Language::parse(language)) {
+            Some(language) => language,
+            None => {
+                println!("Usage: --language <{}>", language_codes(&Language::ALL));
+                return Ok(());
+            }
+        },
+        None => Language::default(),
+    };
+    // dictionaryapi.dev only defines english words, other languages need an api with the same interface
+    let dictionary_url = match args.iter().position(|arg| arg == "--dictionary-url") {
+        Some(index) => match args.get(index + 1) {
+            Some(url) => Some(url.clone()),
+            None => {
+                println!("Usage: --dictionary-url <url>");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    // translations are from the language learned at startup into the native language of the learner
+    let translations: Option<Arc<dyn TranslationBackend>> = match args.iter().position(|arg| arg == "--translations") {
+        Some(index) => match (args.get(index + 1).and_then(|native| Language::parse(native)), args.get(index + 2)) {
+            (Some(native), Some(path)) => match TranslationFile::open(path, language, native) {
+                Ok(file) => Some(Arc::new(file)),
+                Err(error) => {
+                    println!("Couldn't read the translations in '{path}': {error}");
+                    return Ok(());
+                }
+            },
+            _ => {
+                println!("Usage: --translations <{}> <file>", language_codes(&Language::ALL));
+                return Ok(());
+            }
+        },
+        None => None,
+    };
 
-    let dict = Dictionary::new();
+    let (mut dict, mut storage) = match switch_language(&storage, language, dictionary_url.as_deref(), &translations) {
+        Ok(switched) => switched,
+        Err(error) => {
+            println!("{}", describe_dictionary_error(&error));
+            return Ok(());
+        }
+    };
     loop {
         let word = input(">> ")?;
         let word = word.trim();
@@ -27,19 +102,46 @@ async fn main() -> anyhow::Result<()> {
                     break;
                 }
                 "define" | "find" => {
-                    define_word(
-                        &dict,
-                        &storage,
-                        &command_parts.collect::<Vec<&str>>().join(" "),
-                    )
-                    .await?;
+                    define_word(&dict, &storage, &phrase_argument(command_parts)).await?;
                 }
                 "remove" => {
-                    let word = command_parts.collect::<Vec<&str>>().join(" ");
-                    remove_word(&storage, &word).await?;
+                    remove_word(&storage, &phrase_argument(command_parts)).await?;
                 }
                 "practice" => {
-                    practice(&storage, &dict).await?;
+                    let arguments = split_arguments(&word[command.len()..]);
+                    practice_command(&storage, &dict, arguments.iter().map(String::as_str).collect()).await?;
+                }
+                "review" => {
+                    review_command(&storage, &dict, command_parts.collect()).await?;
+                }
+                "discover" => {
+                    discover_command(&storage, &dict, command_parts.collect()).await?;
+                }
+                "wotd" | "word-of-the-day" => {
+                    word_of_the_day(&storage, &dict).await?;
+                }
+                "doctor" => {
+                    doctor(&storage).await?;
+                }
+                "language" => match command_parts.next() {
+                    None => {
+                        println!("Learning {}.", dict.language());
+                        println!("Supported languages: {}", language_codes(&dict.supported_languages()));
+                    }
+                    Some(language) => match Language::parse(language) {
+                        Some(language) => match switch_language(&storage, language, dictionary_url.as_deref(), &translations) {
+                            Ok(switched) => {
+                                (dict, storage) = switched;
+                                println!("Now learning {language}, only {language} words are shown and practiced.");
+                            }
+                            Err(error) => println!("{}", describe_dictionary_error(&error)),
+                        },
+                        None => println!("Usage: language [{}]", language_codes(&Language::ALL)),
+                    },
+                },
+                "deck" | "decks" => {
+                    let arguments = split_arguments(&word[command.len()..]);
+                    deck_command(&storage, arguments.iter().map(String::as_str).collect()).await?;
                 }
                 _ => {
                     println!("Unknown command {command}.");
@@ -50,164 +152,936 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn practice(storage: &Storage, dict: &Dictionary) -> anyhow::Result<()> {
-    let words = storage.select_random_by_score(4).await?;
-    for (index, entry) in words.into_iter().enumerate() {
-        if index != 0 {
+/// A dictionary and a storage for another language, the storage shares the database of the current one.
+/// The translations are only used when they translate from that language.
+fn switch_language(
+    storage: &SqliteStorage,
+    language: Language,
+    dictionary_url: Option<&str>,
+    translations: &Option<Arc<dyn TranslationBackend>>,
+) -> Result<(Dictionary, SqliteStorage), DictionaryError> {
+    let mut builder = Dictionary::builder().language(language);
+    if let Some(url) = dictionary_url {
+        builder = builder.dictionary_url(url);
+    }
+    if let Some(translations) = translations.as_ref().filter(|translations| translations.source_language() == language) {
+        builder = builder.translations(Arc::clone(translations));
+    }
+    Ok((builder.build()?, storage.with_language(language)))
+}
+
+fn language_codes(languages: &[Language]) -> String {
+    languages
+        .iter()
+        .map(|language| language.code())
+        .collect::<Vec<&str>>()
+        .join("|")
+}
+
+async fn practice_command(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    args: Vec<&str>,
+) -> anyhow::Result<()> {
+    let mut deck_name = Vec::new();
+    let mut seed = None;
+    let mut options = PracticeOptions::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--report" {
+            match args.next() {
+                Some(path) => options.report = Some(PathBuf::from(path)),
+                None => {
+                    println!("The report needs a file, e.g. 'practice --report session.md' or 'practice --report session.json'.");
+                    return Ok(());
+                }
+            }
+        } else if arg == "--level" {
+            match args.next().and_then(Level::parse) {
+                Some(level) => options.max_level = Some(level),
+                None => {
+                    println!("The level must be one of A1, A2, B1, B2, C1 or C2, e.g. 'practice --level B2'.");
+                    return Ok(());
+                }
+            }
+        } else if arg == "--timed" {
+            options.time_limits.get_or_insert_with(TimeLimits::default);
+        } else if arg == "--question-time" || arg == "--session-time" {
+            let seconds = match args.next().map(str::parse::<u64>) {
+                Some(Ok(seconds)) if seconds > 0 => Duration::from_secs(seconds),
+                _ => {
+                    println!("The time must be a positive number of seconds, e.g. 'practice {arg} 30'.");
+                    return Ok(());
+                }
+            };
+            let limits = options.time_limits.get_or_insert_with(TimeLimits::default);
+            if arg == "--question-time" {
+                limits.per_question = seconds;
+            } else {
+                limits.session = seconds;
+            }
+        } else if arg == "--seed" {
+            match args.next().map(str::parse::<u64>) {
+                Some(Ok(value)) => seed = Some(value),
+                _ => {
+                    println!("The seed must be a positive number, e.g. 'practice --seed 42'.");
+                    return Ok(());
+                }
+            }
+        } else {
+            deck_name.push(arg);
+        }
+    }
+    // the seed drives which words are practiced, the question kinds, the distractors and the order of the answers
+    let mut rng = match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    };
+    let deck_name = deck_name.join(" ");
+    if deck_name.is_empty() {
+        practice(storage, dict, options, &mut rng).await
+    } else {
+        match storage.get_deck(&deck_name).await? {
+            Some(deck) => {
+                options.deck = Some(deck.uid);
+                practice(storage, dict, options, &mut rng).await
+            }
+            None => {
+                println!("There is no deck named '{deck_name}'.");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PracticeOptions {
+    /// Only practice the words of this deck
+    deck: Option<i64>,
+    /// Where to write the summary of the session
+    report: Option<PathBuf>,
+    /// Makes every question and the whole session run against the clock
+    time_limits: Option<TimeLimits>,
+    /// The most advanced level of the wrong answers
+    max_level: Option<Level>,
+}
+
+/// How many words a session picks, timed sessions pick more since they end when the clock runs out
+const PRACTICE_WORD_COUNT: u32 = 4;
+const TIMED_PRACTICE_WORD_COUNT: u32 = 20;
+
+async fn practice(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    options: PracticeOptions,
+    rng: &mut impl Rng,
+) -> anyhow::Result<()> {
+    let word_count = match options.time_limits {
+        Some(_) => TIMED_PRACTICE_WORD_COUNT,
+        None => PRACTICE_WORD_COUNT,
+    };
+    let words = storage.select_random_by_score(word_count, options.deck, rng).await?;
+    let distractors = DistractorOptions {
+        filter: WordFilter {
+            deck: options.deck,
+            ..Default::default()
+        },
+        // levels come from the english frequency list
+        max_level: options.max_level.filter(|_| dict.language() == Language::English),
+        ..Default::default()
+    };
+    if words.is_empty() {
+        println!("There are no saved words to practice.");
+        return Ok(());
+    }
+    let session = storage.start_session().await?;
+    let mut recorder = SessionRecorder::start();
+    let scores = words
+        .iter()
+        .map(|entry| (entry.uid, entry.score))
+        .collect::<HashMap<i64, i64>>();
+    let mut entries = words.into_iter();
+    let mut question = next_question(storage, dict, &mut entries, &distractors, rng).await?;
+    let mut session_deadline = options
+        .time_limits
+        .map(|limits| Instant::now() + limits.session);
+    let mut first = true;
+    while let Some(current) = question {
+        if session_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            println!("The session time is up.");
+            break;
+        }
+        if !first {
             println!("----------------------------------------");
         }
-        let word = dict.get_definition(&entry.word).await;
-        if let Ok(word) = word {
-            let question = generate_question(storage, dict, entry.uid, &word).await?;
-            ask_question(&storage, question).await?;
-            storage.mark_word_as_quizzed_by_uid(entry.uid).await?;
+        first = false;
+        let word_uid = current.word_uid;
+        let word = current.word.clone();
+        let kind = current.kind;
+        // the next question is generated while the user answers the current one,
+        // it gets an rng of its own so the seeded order of random choices stays the same
+        let mut next_rng = ChaCha8Rng::seed_from_u64(rng.gen());
+        let countdown = options
+            .time_limits
+            .zip(session_deadline)
+            .map(|(limits, deadline)| Countdown::start(limits.per_question, deadline));
+        let asked_at = Instant::now();
+        let (outcome, next) = tokio::join!(
+            async {
+                let outcome = ask_question(storage, dict, current, countdown, rng).await;
+                (outcome, asked_at.elapsed())
+            },
+            next_question(storage, dict, &mut entries, &distractors, &mut next_rng),
+        );
+        let (outcome, mut time) = outcome;
+        let word_outcome = match outcome? {
+            QuestionOutcome::Quit => break,
+            QuestionOutcome::Answered {
+                correct,
+                latency,
+                explained,
+            } => {
+                // the clock is paused while the wrong answer is explained
+                time = time.saturating_sub(explained);
+                if let Some(deadline) = &mut session_deadline {
+                    *deadline += explained;
+                }
+                storage.record_answer(session, word_uid, kind.as_str(), correct, latency).await?;
+                if correct {
+                    WordOutcome::Correct
+                } else {
+                    WordOutcome::Incorrect
+                }
+            }
+            QuestionOutcome::Skipped => WordOutcome::Skipped,
+        };
+        let score_before = scores.get(&word_uid).copied().unwrap_or_default();
+        recorder.record(&word, score_before, word_outcome, time);
+        storage.mark_word_as_quizzed_by_uid(word_uid).await?;
+        question = next?;
+    }
+    let summary = recorder.finish(storage, options.deck).await?;
+    summary.print();
+    if let Some(path) = options.report {
+        match summary.write_to(&path) {
+            Ok(()) => println!("The summary was written to {}.", path.display()),
+            Err(error) => println!("Couldn't write the summary to {}: {error}", path.display()),
         }
     }
     Ok(())
 }
 
-async fn generate_question(
-    storage: &Storage,
+async fn review_command(
+    storage: &impl Storage,
     dict: &Dictionary,
-    uid: i64,
-    word: &Word,
-) -> Result<Question, QuestionGenerationError> {
-    let question_kind = rand::thread_rng().gen_range(0..=2);
-    match question_kind {
-        0 => {
-            let question = generate_question_word_synonym(&storage, &dict, uid, &word, rand::thread_rng().gen_bool(0.5)).await;
-            match question {
-                Ok(question) => Ok(question),
-                Err(QuestionGenerationError::Unsupported) => {
-                    generate_general_question(storage, dict, uid, word).await
+    args: Vec<&str>,
+) -> anyhow::Result<()> {
+    let range = match args[..] {
+        [] => SessionRange::Last(1),
+        ["today"] => SessionRange::Today,
+        [count] => match count.parse::<u32>() {
+            Ok(count) if count > 0 => SessionRange::Last(count),
+            _ => {
+                println!("Usage: review [number of sessions | today]");
+                return Ok(());
+            }
+        },
+        _ => {
+            println!("Usage: review [number of sessions | today]");
+            return Ok(());
+        }
+    };
+    let mistakes = storage.find_mistakes(range).await?;
+    if mistakes.is_empty() {
+        println!("There are no mistakes to review.");
+        return Ok(());
+    }
+    // the same word may have been missed in several kinds of questions
+    let mut reviews: Vec<(i64, String, Vec<QuestionKind>)> = Vec::new();
+    for mistake in mistakes {
+        let kind = QuestionKind::parse(&mistake.question_kind);
+        match reviews.iter_mut().find(|(uid, _, _)| *uid == mistake.word_uid) {
+            Some((_, _, kinds)) => kinds.extend(kind),
+            None => reviews.push((mistake.word_uid, mistake.word, kind.into_iter().collect())),
+        }
+    }
+    review(storage, dict, reviews.into(), &mut StdRng::from_entropy()).await
+}
+
+/// Asks about every word until it was answered correctly,
+/// each time with a different kind of question than the ones it was missed in
+async fn review(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    mut reviews: VecDeque<(i64, String, Vec<QuestionKind>)>,
+    rng: &mut impl Rng,
+) -> anyhow::Result<()> {
+    println!("Reviewing {} words you got wrong.", reviews.len());
+    let session = storage.start_session().await?;
+    let mut first = true;
+    while let Some((uid, word, mut missed_kinds)) = reviews.pop_front() {
+        let definition = match dict.get_definition(&word).await {
+            Ok(definition) => definition,
+            Err(error) => {
+                eprintln!("Skipping '{word}': {}", describe_dictionary_error(&error));
+                continue;
+            }
+        };
+        let distractors = DistractorOptions {
+            difficulty: storage
+                .get_word(&word)
+                .await?
+                .map(|entry| Difficulty::from_score(entry.score))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        let question = match generate_question(storage, dict, uid, &definition, &missed_kinds, &distractors, rng).await {
+            Ok(question) => question,
+            Err(QuestionGenerationError::Dictionary(error)) => {
+                eprintln!("Skipping '{word}' because of a dictionary error: {error}");
+                continue;
+            }
+            Err(QuestionGenerationError::Unsupported) => continue,
+            Err(error) => return Err(error.into()),
+        };
+        if !first {
+            println!("----------------------------------------");
+        }
+        first = false;
+        let kind = question.kind;
+        match ask_question(storage, dict, question, None, rng).await? {
+            QuestionOutcome::Quit => return Ok(()),
+            QuestionOutcome::Answered { correct, latency, .. } => {
+                storage.record_answer(session, uid, kind.as_str(), correct, latency).await?;
+                storage.mark_word_as_quizzed_by_uid(uid).await?;
+                if !correct {
+                    missed_kinds.push(kind);
+                    reviews.push_back((uid, word, missed_kinds));
                 }
-                Err(error) => Err(error),
             }
+            QuestionOutcome::Skipped => reviews.push_back((uid, word, missed_kinds)),
         }
-        1 => generate_question_definition_word(&storage, &dict, uid, word).await,
-        2 => generate_question_word_definition(&storage, &dict, uid, word).await,
-        other => {
-            unreachable!("There is no such question kind {other}");
+        if !reviews.is_empty() {
+            println!("{} words left to review.", reviews.len());
         }
     }
+    println!("You have answered every word correctly.");
+    Ok(())
 }
 
-async fn generate_general_question(
-    storage: &Storage,
+/// Generates a question for the next word that can be found in the dictionary
+async fn next_question(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    entries: &mut impl Iterator<Item = WordEntry>,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> anyhow::Result<Option<Question>> {
+    for entry in entries {
+        if let Ok(word) = dict.get_definition(&entry.word).await {
+            storage.set_parts_of_speech(entry.uid, &word).await?;
+            let distractors = DistractorOptions {
+                difficulty: Difficulty::from_score(entry.score),
+                ..distractors.clone()
+            };
+            match generate_question(storage, dict, entry.uid, &word, &[], &distractors, rng).await {
+                Ok(question) => return Ok(Some(question)),
+                // the dictionary already retried, a single word shouldn't end the whole session
+                Err(QuestionGenerationError::Dictionary(error)) => {
+                    eprintln!("Skipping '{}' because of a dictionary error: {error}", entry.word);
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Generates a question of a random kind, the excluded kinds are only used when no other kind works for the word
+async fn generate_question(
+    storage: &impl Storage,
     dict: &Dictionary,
     uid: i64,
     word: &Word,
+    excluded: &[QuestionKind],
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<Question, QuestionGenerationError> {
-    let question_kind = rand::thread_rng().gen_range(0..=1);
-    match question_kind {
-        0 => generate_question_definition_word(&storage, &dict, uid, word).await,
-        1 => generate_question_word_definition(&storage, &dict, uid, word).await,
-        other => {
-            unreachable!("There is no such question kind {other}");
+    let (mut kinds, mut fallback): (Vec<QuestionKind>, Vec<QuestionKind>) = QuestionKind::ALL
+        .into_iter()
+        .partition(|kind| !excluded.contains(kind));
+    kinds.shuffle(rng);
+    fallback.shuffle(rng);
+    for kind in kinds.into_iter().chain(fallback) {
+        let question = match kind {
+            QuestionKind::Synonym => {
+                generate_question_word_synonym(storage, dict, uid, word, true, distractors, rng).await
+            }
+            QuestionKind::Antonym => {
+                generate_question_word_synonym(storage, dict, uid, word, false, distractors, rng).await
+            }
+            QuestionKind::DefinitionToWord => {
+                generate_question_definition_word(storage, dict, uid, word, distractors, rng).await
+            }
+            QuestionKind::WordToDefinition => {
+                generate_question_word_definition(storage, dict, uid, word, distractors, rng).await
+            }
+            QuestionKind::WordToTranslation => {
+                generate_question_word_translation(storage, dict, uid, word, distractors, rng).await
+            }
+            QuestionKind::TranslationToWord => {
+                generate_question_translation_word(storage, dict, uid, word, distractors, rng).await
+            }
+        };
+        match question {
+            Err(QuestionGenerationError::Unsupported) => continue,
+            question => return question,
         }
     }
+    Err(QuestionGenerationError::Unsupported)
+}
+
+enum QuestionOutcome {
+    Answered {
+        correct: bool,
+        latency: Duration,
+        /// The time spent on the explanation of a wrong answer, which doesn't count against the session clock
+        explained: Duration,
+    },
+    Skipped,
+    Quit,
 }
 
-async fn ask_question(storage: &Storage, mut question: Question) -> Result<(), anyhow::Error> {
+async fn ask_question(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    mut question: Question,
+    countdown: Option<Countdown>,
+    rng: &mut impl Rng,
+) -> Result<QuestionOutcome, anyhow::Error> {
     println!("{}", question.question);
-    question.answers.shuffle(&mut rand::thread_rng());
+    question.answers.shuffle(rng);
     for (index, answer) in question.answers.iter().enumerate() {
         println!("[{}]: {}", index + 1, answer.content);
     }
+    let mut hints = HintState::default();
+    let asked_at = Instant::now();
+    // None when the time ran out before an answer was given
     let answer = loop {
-        let chosen_answer = input("Enter the correct answer: ")?;
-        let chosen_answer = chosen_answer.trim();
-        match chosen_answer.parse::<usize>() {
-            Ok(index) => {
-                if let Some(answer) = question.answers.get(index.wrapping_sub(1)) {
-                    break Some(answer);
+        let chosen_answer = match countdown {
+            Some(countdown) => {
+                let line = match countdown.remaining() {
+                    Some(remaining) => {
+                        let prompt = format!("Enter the correct answer ({}s left): ", remaining.as_secs_f64().ceil());
+                        input_timeout(&prompt, remaining).await?
+                    }
+                    None => None,
+                };
+                match line {
+                    Some(line) => line,
+                    None => {
+                        println!();
+                        println!("Time's up!");
+                        break None;
+                    }
                 }
             }
-            Err(_) => match &chosen_answer.to_lowercase()[..] {
-                ":s" | ":skip" => {
-                    break None;
+            None => input_async("Enter the correct answer: ").await?,
+        };
+        match match_answer(&chosen_answer, &question.answers) {
+            AnswerInput::Choice(index) => break Some(&question.answers[index]),
+            AnswerInput::Command(Command::Skip) => return Ok(QuestionOutcome::Skipped),
+            AnswerInput::Command(Command::Quit) => return Ok(QuestionOutcome::Quit),
+            AnswerInput::Command(Command::Hint(name)) => {
+                if name.is_empty() {
+                    print_hint_help();
+                    continue;
                 }
-                other => {
-                    let mut answers = question
-                        .answers
-                        .iter()
-                        .map(|answer| (answer, strsim::jaro(&answer.content.to_lowercase(), other)))
-                        .collect::<Vec<(&Answer, f64)>>();
-                    // most similar at the start
-                    answers.sort_unstable_by(|(_, a), (_, b)| (-a).partial_cmp(&-b).unwrap());
-                    let difference = f64::abs(answers[0].1 - answers[1].1);
-                    if (answers[0].1 > 0.9 && difference > 0.25) || answers[0].1 == 1.0 {
-                        break Some(answers[0].0);
+                let Some(hint) = Hint::parse(&name) else {
+                    println!("There is no '{name}' hint.");
+                    print_hint_help();
+                    continue;
+                };
+                match hints.reveal(hint, &question, rng) {
+                    Some(message) => {
+                        println!("Hint: {message}");
+                        storage.record_hint_usage(question.word_uid, hint.as_str()).await?;
                     }
+                    None => println!("That hint isn't available for this question."),
                 }
-            },
+            }
+            AnswerInput::Command(Command::Define(word)) => {
+                if word.is_empty() {
+                    println!("Which word would you like to define? e.g. ':define {}'", question.answers[0].content);
+                } else {
+                    match dict.get_lemma_definition(&word).await {
+                        Ok(word) => print_definition(&word),
+                        Err(DictionaryError::NotFound(_)) => {
+                            let saved = storage.list_words().await?;
+                            let suggestions =
+                                suggestions::suggestions(&word, dict.language(), saved.iter().map(|entry| entry.word.as_str()));
+                            if suggestions.is_empty() {
+                                println!("Couldn't find '{word}'.");
+                            } else {
+                                println!("Couldn't find '{word}', did you mean {}?", suggestions.join(", "));
+                            }
+                        }
+                        Err(error) => println!("{}", describe_dictionary_error(&error)),
+                    }
+                }
+            }
+            AnswerInput::Command(Command::Help) => print_answer_help(),
+            AnswerInput::Ambiguous(indices) => {
+                let options = indices
+                    .iter()
+                    .map(|index| format!("[{}]", index + 1))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                println!("Your answer matches more than one option ({options}), please be more specific.");
+            }
+            AnswerInput::Unknown => {
+                println!("Couldn't understand your answer, please try again. Enter ':help' to see what you can do.");
+            }
         }
-        println!("Couldn't understand your answer, please try again.");
     };
-    if let Some(answer) = answer {
-        if answer.correct {
+    let latency = asked_at.elapsed();
+    let correct = answer.is_some_and(|answer| answer.correct);
+    if correct {
+        if hints.used().is_empty() {
             println!("The answer is correct. Well done!");
-            storage
-                .multiply_score_by_uid(question.word_uid, 0.92)
-                .await?;
         } else {
-            println!(
-                "The answer is incorrect. The right answer is {}. ",
-                question
-                    .answers
-                    .iter()
-                    .find(|answer| answer.correct)
-                    .map(|answer| &answer.content[..])
-                    .unwrap_or("unknown")
-            );
-            let modifier = 1.04;
-            storage
-                .multiply_score_by_uid(question.word_uid, modifier)
-                .await?;
-            if let Some(word_uid) = answer.word_uid {
-                storage.multiply_score_by_uid(word_uid, modifier).await?;
+            println!("The answer is correct, partial credit for using hints.");
+        }
+        let modifier = match countdown {
+            Some(countdown) => countdown.speed_modifier(0.92, latency),
+            None => 0.92,
+        };
+        storage
+            .multiply_score_by_uid(question.word_uid, hints.reward(modifier))
+            .await?;
+    } else {
+        let right_answer = question
+            .answers
+            .iter()
+            .find(|answer| answer.correct)
+            .map(|answer| &answer.content[..])
+            .unwrap_or("unknown");
+        match answer {
+            Some(_) => println!("The answer is incorrect. The right answer is {right_answer}. "),
+            None => println!("The right answer is {right_answer}."),
+        }
+        let modifier = 1.04;
+        storage
+            .multiply_score_by_uid(question.word_uid, modifier)
+            .await?;
+        if let Some(word_uid) = answer.and_then(|answer| answer.word_uid) {
+            storage.multiply_score_by_uid(word_uid, modifier).await?;
+        }
+        explain_answers(storage, dict, &question).await?;
+    }
+    let explained = asked_at.elapsed() - latency;
+    Ok(QuestionOutcome::Answered {
+        correct,
+        latency,
+        explained,
+    })
+}
+
+/// How many dictionary lookups the explanation of a question may run at once
+const EXPLANATION_LOOKUP_CONCURRENCY: usize = 4;
+
+/// Shows what the word of the question and every option mean after a wrong answer,
+/// then offers to save the options that aren't saved yet
+async fn explain_answers(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    question: &Question,
+) -> anyhow::Result<()> {
+    // definition options already explain themselves, only the words need to be looked up
+    let lookups = std::iter::once(&question.word)
+        .chain(
+            question
+                .answers
+                .iter()
+                .filter(|answer| answer.content == answer.word && answer.word != question.word)
+                .map(|answer| &answer.word),
+        )
+        .collect::<Vec<&String>>();
+    let definitions = stream::iter(lookups.iter())
+        .map(|word| dict.get_definition(word))
+        .buffered(EXPLANATION_LOOKUP_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+    let definitions = lookups.into_iter().zip(definitions).collect::<HashMap<_, _>>();
+    let describe = |word: &String, part_of_speech: Option<&PartOfSpeech>| {
+        match definitions.get(word) {
+            Some(Ok(definition)) => brief_definition(definition, part_of_speech),
+            Some(Err(error)) => describe_dictionary_error(error),
+            None => "no definition found".to_owned(),
+        }
+    };
+
+    println!("'{}': {}", question.word, describe(&question.word, question.hints.part_of_speech.as_ref()));
+    for (index, answer) in question.answers.iter().enumerate() {
+        if answer.word == question.word {
+            continue;
+        }
+        if answer.content == answer.word {
+            println!("    [{}] {}: {}", index + 1, answer.word, describe(&answer.word, None));
+        } else {
+            println!("    [{}] is a definition of '{}'", index + 1, answer.word);
+        }
+    }
+
+    let unsaved = question
+        .answers
+        .iter()
+        .enumerate()
+        .filter(|(_, answer)| answer.word_uid.is_none() && answer.word != question.word)
+        .collect::<Vec<_>>();
+    if unsaved.is_empty() {
+        return Ok(());
+    }
+    let options = unsaved
+        .iter()
+        .map(|(index, answer)| format!("[{}] {}", index + 1, answer.word))
+        .collect::<Vec<String>>()
+        .join(", ");
+    println!("These words aren't saved yet: {options}");
+    let chosen = input_async("Enter the numbers of the words you want to practice, or leave empty to continue: ").await?;
+    for number in chosen.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()) {
+        let answer = number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| unsaved.iter().find(|(index, _)| index + 1 == number));
+        match answer {
+            Some((_, answer)) => {
+                if storage.add_word(&answer.word).await? {
+                    println!("Saved '{}'.", answer.word);
+                }
             }
+            None => println!("'{number}' isn't one of the unsaved words, ignoring it."),
         }
     }
     Ok(())
 }
 
-async fn define_word(dict: &Dictionary, storage: &Storage, word: &str) -> anyhow::Result<()> {
-    match dict.get_definition(&word).await {
-        Ok(word) => {
-            let multiple_search_score = 5;
-            print_definition(&word);
-            let modified: bool = storage
-                .add_score_to_optional(&word.word, multiple_search_score)
-                .await?;
-            if !modified {
-                let practice = input("Would you like to practice this word? (Y/n): ").unwrap();
-                let save = str_to_bool(practice).unwrap_or(false);
-                if save {
-                    match storage.add_word(&word.word).await {
-                        Ok(_) => {
-                            println!("Saved the word successfully");
-                        }
-                        Err(error) => {
-                            eprintln!("Failed to save the word: {error}");
-                        }
-                    }
+/// The first definition of a word, preferring the given part of speech
+fn brief_definition(word: &Word, part_of_speech: Option<&PartOfSpeech>) -> String {
+    let meaning = part_of_speech
+        .and_then(|part_of_speech| {
+            word.meanings
+                .iter()
+                .find(|meaning| meaning.part_of_speech == *part_of_speech)
+        })
+        .or_else(|| word.meanings.first());
+    meaning
+        .and_then(|meaning| {
+            meaning.definitions.first().map(|definition| {
+                format!("({}) {}", meaning.part_of_speech.as_str(), definition.definition)
+            })
+        })
+        .unwrap_or_else(|| "no definition found".to_owned())
+}
+
+fn print_hint_help() {
+    println!("Available hints:");
+    for hint in Hint::ALL {
+        println!("    {}", hint.usage());
+    }
+}
+
+fn print_answer_help() {
+    println!("Answer with the number or the letter of an option (1 or a), or type the answer itself.");
+    println!("You can also use the following commands:");
+    println!("    :hint <name>    get a hint, each hint used gives less credit for the answer");
+    println!("    :define <word>  look up a word without leaving the question");
+    println!("    :skip           skip this question");
+    println!("    :quit           stop practicing");
+}
+
+async fn define_word(dict: &Dictionary, storage: &impl Storage, word: &str) -> anyhow::Result<()> {
+    let mut searched = dictionary::normalize(word);
+    let word = loop {
+        match dict.get_lemma_definition(&searched).await {
+            Ok(word) => break word,
+            Err(DictionaryError::NotFound(_)) => {
+                let saved = storage.list_words().await?;
+                let suggestions = suggestions::suggestions(&searched, dict.language(), saved.iter().map(|entry| entry.word.as_str()));
+                match choose_suggestion(&searched, &suggestions)? {
+                    Some(suggestion) => searched = suggestion,
+                    None => return Ok(()),
                 }
             }
+            Err(error) => {
+                println!("{}", describe_dictionary_error(&error));
+                return Ok(());
+            }
         }
-        Err(error) => match error {
-            dictionary::DictionaryError::NotFound(_) => {
-                println!("Couldn't find the word you were looking for.")
+    };
+    let multiple_search_score = 5;
+    if word.word != searched {
+        println!("'{searched}' is a form of '{}'.", word.word);
+    }
+    print_definition(&word);
+    let modified: bool = storage
+        .add_score_to_optional(&word.word, multiple_search_score)
+        .await?;
+    if !modified {
+        let practice = input("Would you like to practice this word? (Y/n): ").unwrap();
+        let save = str_to_bool(practice).unwrap_or(false);
+        if save {
+            match storage.add_word(&word.word).await {
+                Ok(true) => {
+                    println!("Saved the word successfully");
+                }
+                Ok(false) => {
+                    println!("This word is already saved.");
+                }
+                Err(error) => {
+                    eprintln!("Failed to save the word: {error}");
+                }
             }
-            other => {
-                println!("Encountered an error while searching for the word definition: {other:?}");
+        }
+    }
+    if let Some(entry) = storage.get_word(&word.word).await? {
+        storage.set_parts_of_speech(entry.uid, &word).await?;
+    }
+    Ok(())
+}
+
+/// Offers the suggestions for a word that wasn't found, returns the one the user picked.
+/// A suggestion can be picked by its number or by typing it.
+fn choose_suggestion(searched: &str, suggestions: &[String]) -> io::Result<Option<String>> {
+    if suggestions.is_empty() {
+        println!("Couldn't find '{searched}'.");
+        return Ok(None);
+    }
+    println!("Couldn't find '{searched}', did you mean:");
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        println!("  {}. {suggestion}", index + 1);
+    }
+    let choice = input("Pick a word, or press enter to cancel: ")?;
+    let choice = choice.trim();
+    let picked = match choice.parse::<usize>() {
+        Ok(number) => number.checked_sub(1).and_then(|index| suggestions.get(index)),
+        Err(_) => suggestions
+            .iter()
+            .find(|suggestion| suggestion.eq_ignore_ascii_case(choice)),
+    };
+    Ok(picked.cloned())
+}
+
+/// How many words `discover` proposes when no count is given
+const DISCOVER_WORD_COUNT: usize = 5;
+/// How many more candidates than needed are looked up, some of them can't be defined
+const CANDIDATE_LOOKUP_FACTOR: usize = 3;
+/// How many candidates are looked up at once while discovering words
+const DISCOVER_LOOKUP_CONCURRENCY: usize = 4;
+/// How many of the candidates for the word of the day are tried before giving up
+const WORD_OF_THE_DAY_ATTEMPTS: usize = 10;
+
+async fn discover_command(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    args: Vec<&str>,
+) -> anyhow::Result<()> {
+    if dict.language() != Language::English {
+        println!("Discovering words only works for English so far.");
+        return Ok(());
+    }
+    let mut level = None;
+    let mut length = None;
+    let mut part_of_speech = None;
+    let mut count = DISCOVER_WORD_COUNT;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        let valid = match (arg, value) {
+            ("--level", Some(value)) => Level::parse(value).map(|value| level = Some(value)),
+            ("--length", Some(value)) => value.parse().ok().map(|value| length = Some(value)),
+            ("--pos", Some(value)) => PartOfSpeech::try_from(value.to_lowercase())
+                .ok()
+                .map(|value| part_of_speech = Some(value)),
+            ("--count", Some(value)) => value
+                .parse()
+                .ok()
+                .filter(|value| *value > 0)
+                .map(|value| count = value),
+            _ => None,
+        };
+        if valid.is_none() {
+            println!("Usage: discover [--level <A1-C2>] [--length <letters>] [--pos <part of speech>] [--count <words>]");
+            return Ok(());
+        }
+    }
+
+    let saved_entries = storage.list_words().await?;
+    let level = level.unwrap_or_else(|| discovery::estimate_level(&saved_entries));
+    let saved = saved_entries
+        .into_iter()
+        .map(|entry| entry.word.to_lowercase())
+        .collect::<HashSet<String>>();
+    let mut rng = StdRng::from_entropy();
+    let candidates = match level {
+        // the rarest words aren't in the frequency list, so they are picked at random
+        Level::C2 => {
+            let mut query = RandomWordQuery::new(count * CANDIDATE_LOOKUP_FACTOR).exclude(&saved);
+            if let Some(length) = length {
+                query = query.length(length);
             }
-        },
+            dict.random_words(&query).await.map(|words| {
+                words
+                    .into_iter()
+                    .filter(|word| Level::of(word) == Level::C2)
+                    .collect::<Vec<String>>()
+            })
+        }
+        level => Ok(discovery::candidates(level, length, &saved, &mut rng)
+            .into_iter()
+            .take(count * CANDIDATE_LOOKUP_FACTOR)
+            .map(str::to_owned)
+            .collect()),
+    };
+    let candidates = match candidates {
+        Ok(candidates) => candidates,
+        Err(error) => {
+            println!("{}", describe_dictionary_error(&error));
+            return Ok(());
+        }
+    };
+    let words = stream::iter(candidates.iter())
+        .map(|word| dict.get_definition(word))
+        .buffered(DISCOVER_LOOKUP_CONCURRENCY)
+        .filter_map(|word| async {
+            word.ok().filter(|word| {
+                part_of_speech.as_ref().is_none_or(|part_of_speech| {
+                    word.meanings
+                        .iter()
+                        .any(|meaning| meaning.part_of_speech == *part_of_speech)
+                })
+            })
+        })
+        .take(count)
+        .collect::<Vec<Word>>()
+        .await;
+    if words.is_empty() {
+        println!("Couldn't find new words at level {level}, try other filters.");
+        return Ok(());
+    }
+
+    println!("New words at level {level}:");
+    for (index, word) in words.iter().enumerate() {
+        println!("    [{}] {}: {}", index + 1, word.word, brief_definition(word, part_of_speech.as_ref()));
+    }
+    let chosen = input_async("Enter the numbers of the words you want to practice, 'all', or leave empty to continue: ").await?;
+    let chosen = chosen.trim();
+    let chosen = if chosen.eq_ignore_ascii_case("all") {
+        words.iter().collect::<Vec<&Word>>()
+    } else {
+        let mut chosen_words = Vec::new();
+        for number in chosen.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()) {
+            match number.parse::<usize>().ok().and_then(|number| words.get(number.wrapping_sub(1))) {
+                Some(word) => chosen_words.push(word),
+                None => println!("'{number}' isn't one of the words, ignoring it."),
+            }
+        }
+        chosen_words
+    };
+    for word in chosen {
+        save_word(storage, word).await?;
+    }
+    Ok(())
+}
+
+async fn word_of_the_day(storage: &impl Storage, dict: &Dictionary) -> anyhow::Result<()> {
+    if dict.language() != Language::English {
+        println!("The word of the day is only picked for English so far.");
+        return Ok(());
+    }
+    let today = Local::now().date_naive();
+    for candidate in discovery::word_of_the_day_candidates(today)
+        .into_iter()
+        .take(WORD_OF_THE_DAY_ATTEMPTS)
+    {
+        let word = match dict.get_definition(candidate).await {
+            Ok(word) => word,
+            Err(DictionaryError::NotFound(_)) => continue,
+            Err(error) => {
+                println!("{}", describe_dictionary_error(&error));
+                return Ok(());
+            }
+        };
+        println!("The word of the day for {today} is '{}'.", word.word);
+        print_definition(&word);
+        if storage.get_word(&word.word).await?.is_none() {
+            let practice = input_async("Would you like to practice this word? (Y/n): ").await?;
+            if str_to_bool(practice).unwrap_or(false) {
+                save_word(storage, &word).await?;
+            }
+        }
+        return Ok(());
+    }
+    println!("Couldn't find the word of the day, please try again later.");
+    Ok(())
+}
+
+/// Saves a word along with its parts of speech
+async fn save_word(storage: &impl Storage, word: &Word) -> anyhow::Result<()> {
+    if storage.add_word(&word.word).await? {
+        println!("Saved '{}'.", word.word);
+    } else {
+        println!("'{}' is already saved.", word.word);
+    }
+    if let Some(entry) = storage.get_word(&word.word).await? {
+        storage.set_parts_of_speech(entry.uid, word).await?;
     }
     Ok(())
 }
 
-async fn remove_word(storage: &Storage, word: &str) -> sqlx::Result<()> {
+/// The rest of the command line as a single word or phrase, quotes around a phrase are optional
+fn phrase_argument<'a>(parts: impl Iterator<Item = &'a str>) -> String {
+    let phrase = parts.collect::<Vec<&str>>().join(" ");
+    phrase.trim_matches(|c| c == '"' || c == '\'').to_owned()
+}
+
+/// Explains a dictionary error in a way that tells the user what they can do about it
+fn describe_dictionary_error(error: &DictionaryError) -> String {
+    match error {
+        DictionaryError::NotFound(_) => "Couldn't find the word you were looking for.".to_owned(),
+        DictionaryError::Timeout(_) => {
+            "The dictionary took too long to answer, please try again later.".to_owned()
+        }
+        DictionaryError::Connection(_) | DictionaryError::Client(_) => {
+            "Couldn't reach the dictionary, please check your internet connection.".to_owned()
+        }
+        DictionaryError::RateLimited {
+            retry_after: Some(retry_after),
+        } => format!(
+            "The dictionary received too many requests, please try again in {} seconds.",
+            retry_after.as_secs().max(1)
+        ),
+        DictionaryError::RateLimited { retry_after: None } => {
+            "The dictionary received too many requests, please try again in a bit.".to_owned()
+        }
+        DictionaryError::Status { status, .. } => {
+            format!("The dictionary is having problems right now (status {status}), please try again later.")
+        }
+        DictionaryError::MalformedPayload(_)
+        | DictionaryError::EmptyResponse
+        | DictionaryError::UnknownPartOfSpeech { .. } => {
+            format!("The dictionary answered with something that couldn't be understood: {error}.")
+        }
+        DictionaryError::NotEnoughWords { .. } => {
+            "Couldn't find enough random words matching the filters, try other filters.".to_owned()
+        }
+        DictionaryError::UnsupportedLanguage(language) => {
+            format!("{language} isn't supported by the dictionary api, start with '--dictionary-url <url>' to use one that defines {language} words.")
+        }
+    }
+}
+
+async fn remove_word(storage: &impl Storage, word: &str) -> StorageResult<()> {
     if storage.remove_word(word).await? {
         println!("Deleted the word successfully.");
     } else {
@@ -216,8 +1090,173 @@ async fn remove_word(storage: &Storage, word: &str) -> sqlx::Result<()> {
     Ok(())
 }
 
+async fn doctor(storage: &SqliteStorage) -> anyhow::Result<()> {
+    let report = storage.check_integrity().await?;
+    if report.is_healthy() {
+        println!("No problems were found in the database.");
+        return Ok(());
+    }
+    println!("Found the following problems in the database:");
+    for problem in report.problems() {
+        println!("    {problem}");
+    }
+    let repair = input("Would you like to repair the database? (Y/n): ")?;
+    if str_to_bool(repair).unwrap_or(false) {
+        storage.repair().await?;
+        let report = storage.check_integrity().await?;
+        if report.is_healthy() {
+            println!("Repaired the database successfully.");
+        } else {
+            println!("Some problems couldn't be repaired, consider restoring the database from a backup:");
+            for problem in report.problems() {
+                println!("    {problem}");
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn deck_command(storage: &impl Storage, args: Vec<&str>) -> anyhow::Result<()> {
+    match args[..] {
+        [] | ["list"] => {
+            let decks = storage.list_decks().await?;
+            if decks.is_empty() {
+                println!("There are no decks yet, create one with 'deck create <name>'.");
+            }
+            for deck in decks {
+                println!("{}", deck.name);
+            }
+        }
+        // names of more than one word have to be quoted when other arguments follow them
+        ["create", ref name @ ..] if !name.is_empty() => {
+            let name = name.join(" ");
+            if storage.create_deck(&name).await? {
+                println!("Created the deck '{name}'.");
+            } else {
+                println!("A deck named '{name}' already exists.");
+            }
+        }
+        ["rename", name, new_name] => {
+            if storage.get_deck(new_name).await?.is_some() {
+                println!("A deck named '{new_name}' already exists.");
+            } else if storage.rename_deck(name, new_name).await? {
+                println!("Renamed the deck '{name}' to '{new_name}'.");
+            } else {
+                println!("There is no deck named '{name}'.");
+            }
+        }
+        ["delete", ref name @ ..] if !name.is_empty() => {
+            let name = name.join(" ");
+            if storage.delete_deck(&name).await? {
+                println!("Deleted the deck '{name}'.");
+            } else {
+                println!("There is no deck named '{name}'.");
+            }
+        }
+        ["show", ref name @ ..] if !name.is_empty() => {
+            let name = name.join(" ");
+            let Some(deck) = storage.get_deck(&name).await? else {
+                println!("There is no deck named '{name}'.");
+                return Ok(());
+            };
+            let words = storage.get_deck_words(deck.uid).await?;
+            if words.is_empty() {
+                println!("The deck '{name}' is empty.");
+            }
+            for word in words {
+                let saved = word
+                    .created_at
+                    .map_or_else(|| "unknown".to_owned(), |created_at| created_at.to_string());
+                println!(
+                    "{} (score: {}, saved: {saved}, last practiced: {})",
+                    word.word, word.score, word.last_quizzed
+                );
+            }
+        }
+        ["add", name, ref word @ ..] if !word.is_empty() => {
+            let word = word.join(" ");
+            let Some((deck, entry)) = find_deck_and_word(storage, name, &word).await? else {
+                return Ok(());
+            };
+            if storage.add_word_to_deck(deck.uid, entry.uid).await? {
+                println!("Added '{word}' to the deck '{name}'.");
+            } else {
+                println!("'{word}' is already in the deck '{name}'.");
+            }
+        }
+        ["remove", name, ref word @ ..] if !word.is_empty() => {
+            let word = word.join(" ");
+            let Some((deck, entry)) = find_deck_and_word(storage, name, &word).await? else {
+                return Ok(());
+            };
+            if storage.remove_word_from_deck(deck.uid, entry.uid).await? {
+                println!("Removed '{word}' from the deck '{name}'.");
+            } else {
+                println!("'{word}' is not in the deck '{name}'.");
+            }
+        }
+        ["move", from, to, ref word @ ..] if !word.is_empty() => {
+            let word = word.join(" ");
+            let Some((from_deck, entry)) = find_deck_and_word(storage, from, &word).await? else {
+                return Ok(());
+            };
+            let Some(to_deck) = storage.get_deck(to).await? else {
+                println!("There is no deck named '{to}'.");
+                return Ok(());
+            };
+            if storage
+                .move_word_between_decks(from_deck.uid, to_deck.uid, entry.uid)
+                .await?
+            {
+                println!("Moved '{word}' from the deck '{from}' to '{to}'.");
+            } else {
+                println!("'{word}' is not in the deck '{from}'.");
+            }
+        }
+        _ => {
+            println!("Usage:");
+            println!("    deck list");
+            println!("    deck create <name>");
+            println!("    deck rename <name> <new name>");
+            println!("    deck delete <name>");
+            println!("    deck show <name>");
+            println!("    deck add <name> <word>");
+            println!("    deck remove <name> <word>");
+            println!("    deck move <from> <to> <word>");
+            println!("Names of more than one word can be quoted, e.g. deck rename \"phrasal verbs\" verbs");
+        }
+    }
+    Ok(())
+}
+
+/// Looks up a deck and a saved word, printing a message and returning None if one of them is missing
+async fn find_deck_and_word(
+    storage: &impl Storage,
+    deck: &str,
+    word: &str,
+) -> StorageResult<Option<(DeckEntry, WordEntry)>> {
+    let Some(deck_entry) = storage.get_deck(deck).await? else {
+        println!("There is no deck named '{deck}'.");
+        return Ok(None);
+    };
+    let Some(word_entry) = storage.get_word(word).await? else {
+        println!("The word '{word}' is not saved.");
+        return Ok(None);
+    };
+    Ok(Some((deck_entry, word_entry)))
+}
+
 fn print_definition(word: &Word) {
     println!("Showing definition for '{}':", word.word);
+    match (word.level(), word.frequency_rank()) {
+        (Some(level), Some(rank)) => println!("    level {level}, ranked {rank} among the most common words"),
+        (Some(Level::C2), None) => println!("    level {}, a rare word", Level::C2),
+        (Some(level), None) => println!("    level {level}, a less common word"),
+        (None, _) => {}
+    }
+    if !word.translations.is_empty() {
+        println!("    translations: {}", word.translations.join(", "));
+    }
     for meaning in &word.meanings {
         println!("    {:?}:", meaning.part_of_speech);
         for definition in &meaning.definitions {
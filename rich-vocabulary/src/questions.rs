@@ -1,18 +1,126 @@
-use std::{error::Error, fmt::Display};
+use std::{error::Error, fmt::Display, pin::pin};
 
-use dictionary::{Dictionary, DictionaryError, PartOfSpeech, Word, WordDefinition, WordMeaning};
+use dictionary::{
+    inflections, is_phrase, normalize, phrases, same_lemma, words_in_levels, Dictionary, DictionaryError, Language, Level,
+    PartOfSpeech, RandomWordQuery, Word, WordDefinition, WordMeaning,
+};
+use futures::{stream, StreamExt};
 use rand::{
     seq::{IteratorRandom, SliceRandom},
     Rng,
 };
 
-use crate::storage::Storage;
+use crate::distractors::{self, DistractorOptions};
+use crate::storage::{Storage, StorageError, StorageResult};
 
 #[derive(Debug, Clone)]
 pub struct Question {
     pub word_uid: i64,
+    /// The word the question is about
+    pub word: String,
+    pub kind: QuestionKind,
     pub question: String,
     pub answers: Vec<Answer>,
+    pub hints: QuestionHints,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionKind {
+    /// Pick the synonym of a word
+    Synonym,
+    /// Pick the antonym of a word
+    Antonym,
+    /// Pick the word that matches a definition
+    DefinitionToWord,
+    /// Pick the definition of a word
+    WordToDefinition,
+    /// Pick the translation of a word into the native language
+    WordToTranslation,
+    /// Pick the word that means a word of the native language
+    TranslationToWord,
+}
+
+impl QuestionKind {
+    pub const ALL: [QuestionKind; 6] = [
+        QuestionKind::Synonym,
+        QuestionKind::Antonym,
+        QuestionKind::DefinitionToWord,
+        QuestionKind::WordToDefinition,
+        QuestionKind::WordToTranslation,
+        QuestionKind::TranslationToWord,
+    ];
+
+    /// The name the kind is stored with
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionKind::Synonym => "synonym",
+            QuestionKind::Antonym => "antonym",
+            QuestionKind::DefinitionToWord => "definition_to_word",
+            QuestionKind::WordToDefinition => "word_to_definition",
+            QuestionKind::WordToTranslation => "word_to_translation",
+            QuestionKind::TranslationToWord => "translation_to_word",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        QuestionKind::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+/// What the hints of a question can reveal about the word it asks about
+#[derive(Debug, Clone, Default)]
+pub struct QuestionHints {
+    pub part_of_speech: Option<PartOfSpeech>,
+    /// An example sentence with the word blanked out
+    pub example: Option<String>,
+}
+
+impl QuestionHints {
+    /// Hints from the first meaning of the word, for questions that aren't about a particular meaning
+    fn of_word(word: &Word) -> Self {
+        word.meanings
+            .first()
+            .and_then(|meaning| Some(Self::new(word, meaning, meaning.definitions.first()?)))
+            .unwrap_or_default()
+    }
+
+    fn new(word: &Word, meaning: &WordMeaning, definition: &WordDefinition) -> Self {
+        Self {
+            part_of_speech: Some(meaning.part_of_speech.clone()),
+            example: definition
+                .example
+                .as_ref()
+                .map(|example| blank_out(example, &word.word)),
+        }
+    }
+}
+
+/// Replaces every occurrence of a word or one of its forms in a sentence with a blank, ignoring case
+fn blank_out(sentence: &str, word: &str) -> String {
+    let mut forms = inflections(word);
+    forms.push(normalize(word));
+    // longer forms first so `running` isn't blanked as `____ning`
+    forms.sort_by_key(|form| std::cmp::Reverse(form.len()));
+    forms
+        .iter()
+        .fold(sentence.to_owned(), |sentence, form| blank_out_form(&sentence, form))
+}
+
+fn blank_out_form(sentence: &str, word: &str) -> String {
+    let lowercase = sentence.to_lowercase();
+    // lowercasing can change byte offsets of non ascii text, in which case the sentence is kept as is
+    if word.is_empty() || lowercase.len() != sentence.len() {
+        return sentence.to_owned();
+    }
+    let mut blanked = String::with_capacity(sentence.len());
+    let mut last = 0;
+    for (start, _) in lowercase.match_indices(word) {
+        blanked.push_str(&sentence[last..start]);
+        blanked.push_str("____");
+        last = start + word.len();
+    }
+    blanked.push_str(&sentence[last..]);
+    blanked
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +128,8 @@ pub struct Answer {
     pub content: String,
     pub correct: bool,
     pub word_uid: Option<i64>,
+    /// The word behind the answer, for definitions this is the word being defined
+    pub word: String,
 }
 
 #[derive(Debug)]
@@ -27,7 +137,7 @@ pub enum QuestionGenerationError {
     /// This error kind means that the current format is not supported for this word
     /// It usually happen when the word lacks synonyms and antonyms
     Unsupported,
-    Storage(sqlx::Error),
+    Storage(StorageError),
     Dictionary(DictionaryError),
 }
 
@@ -42,15 +152,20 @@ impl Display for QuestionGenerationError {
     }
 }
 
+/// How many dictionary lookups for distractor definitions may be in flight at once
+const DEFINITION_LOOKUP_CONCURRENCY: usize = 6;
+
 /// If synonym is true the correct answer will be a synonym otherwise its gonna be an antonym
 pub async fn generate_question_word_synonym(
-    storage: &Storage,
+    storage: &impl Storage,
     dict: &Dictionary,
     word_uid: i64,
     word: &Word,
     is_synonym: bool,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<Question, QuestionGenerationError> {
-    let (meaning, definition) = word
+    let candidates = word
         .meanings
         .iter()
         .map(|meaning| {
@@ -63,17 +178,16 @@ pub async fn generate_question_word_synonym(
                         (!definition.synonyms.is_empty() || !meaning.synonyms.is_empty())
                             && (!definition.antonyms.is_empty() || !meaning.definitions.is_empty())
                     })
-                    .choose(&mut rand::thread_rng()),
+                    .choose(&mut *rng),
             )
         })
+        .collect::<Vec<_>>();
+    let (meaning, definition) = candidates
+        .into_iter()
         .filter_map(|(meaning, definition)| {
-            if let Some(definition) = definition {
-                Some((meaning, definition))
-            } else {
-                None
-            }
+            definition.map(|definition| (meaning, definition))
         })
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
     let answer_count = 4;
     let mut answers = Vec::with_capacity(answer_count);
@@ -81,68 +195,80 @@ pub async fn generate_question_word_synonym(
         .synonyms
         .iter()
         .chain(definition.synonyms.iter())
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
     answers.push(Answer {
         content: synonym.clone(),
         correct: is_synonym,
         word_uid: storage
-            .get_word(&synonym)
+            .get_word(synonym)
             .await
             .map_err(QuestionGenerationError::Storage)?
             .map(|word| word.uid),
+        word: synonym.clone(),
     });
     let antonym = meaning
         .antonyms
         .iter()
         .chain(definition.antonyms.iter())
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
     answers.push(Answer {
         content: antonym.clone(),
         correct: !is_synonym,
         word_uid: storage
-            .get_word(&antonym)
+            .get_word(antonym)
             .await
             .map_err(QuestionGenerationError::Storage)?
             .map(|word| word.uid),
+        word: antonym.clone(),
     });
-    let mut invalid_words = word
+    let invalid_words = word
         .all_synonyms()
         .chain(word.all_antonyms())
         .chain(Some(&word.word[..]))
         .collect::<Vec<&str>>();
+    let distractors = distractors.with_part_of_speech(meaning.part_of_speech.clone());
     let existing_words_count = answer_count - answers.len();
-    add_from_storage(storage, &mut answers, &invalid_words, existing_words_count)
+    add_from_storage(storage, &mut answers, &invalid_words, existing_words_count, &distractors, rng)
         .await
         .map_err(QuestionGenerationError::Storage)?;
 
     let random_words_count = answer_count - answers.len();
-    add_random(dict, &mut answers, &mut invalid_words, random_words_count)
+    add_random(dict, &mut answers, &invalid_words, random_words_count, &distractors, rng)
         .await
         .map_err(QuestionGenerationError::Dictionary)?;
     let synonym_or_antonym = if is_synonym { "synonym" } else { "antonym" };
     Ok(Question {
         word_uid,
+        word: word.word.clone(),
+        kind: if is_synonym {
+            QuestionKind::Synonym
+        } else {
+            QuestionKind::Antonym
+        },
         question: format!("What is the {synonym_or_antonym} of {}?", word.word),
         answers,
+        hints: QuestionHints::new(word, meaning, definition),
     })
 }
 
 pub async fn generate_question_definition_word(
-    storage: &Storage,
+    storage: &impl Storage,
     dict: &Dictionary,
     uid: i64,
     word: &Word,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<Question, QuestionGenerationError> {
     // question kind: match the definition to the correct word
     let meaning: &WordMeaning = word
         .meanings
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
     let definition: &WordDefinition = meaning
         .definitions
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
 
     let answers_count = 4;
@@ -152,63 +278,71 @@ pub async fn generate_question_definition_word(
         content: word.word.clone(),
         correct: true,
         word_uid: Some(uid),
+        word: word.word.clone(),
     });
     invalid_words.push(&word.word[..]);
     let antonym_answer = definition
         .antonyms
-        .choose(&mut rand::thread_rng())
-        .or_else(|| meaning.antonyms.choose(&mut rand::thread_rng()));
+        .choose(rng)
+        .or_else(|| meaning.antonyms.choose(&mut *rng));
     if let Some(anonym) = antonym_answer {
-        if rand::thread_rng().gen_bool(0.6) {
+        if rng.gen_bool(0.6) {
             answers.push(Answer {
                 content: anonym.to_owned(),
                 correct: false,
                 word_uid: storage
-                    .get_word(&anonym)
+                    .get_word(anonym)
                     .await
                     .map_err(QuestionGenerationError::Storage)?
                     .map(|word| word.uid),
+                word: anonym.to_owned(),
             });
             invalid_words.push(anonym);
         }
     }
     invalid_words.extend(word.all_synonyms());
 
+    let distractors = distractors.with_part_of_speech(meaning.part_of_speech.clone());
     let max_existing_words = answers_count - answers.len();
-    let existing_words_limit = rand::thread_rng().gen_range(1..=max_existing_words);
-    add_from_storage(storage, &mut answers, &invalid_words, existing_words_limit)
+    let existing_words_limit = rng.gen_range(1..=max_existing_words);
+    add_from_storage(storage, &mut answers, &invalid_words, existing_words_limit, &distractors, rng)
         .await
         .map_err(QuestionGenerationError::Storage)?;
 
     let random_words_count = answers_count - answers.len();
-    add_random(dict, &mut answers, &invalid_words, random_words_count)
+    add_random(dict, &mut answers, &invalid_words, random_words_count, &distractors, rng)
         .await
         .map_err(QuestionGenerationError::Dictionary)?;
 
     Ok(Question {
         word_uid: uid,
+        word: word.word.clone(),
+        kind: QuestionKind::DefinitionToWord,
         question: format!(
             "What word matches the following definition? {:?}",
             definition.definition
         ),
         answers,
+        hints: QuestionHints::new(word, meaning, definition),
     })
 }
 
 pub async fn generate_question_word_definition(
-    storage: &Storage,
+    storage: &impl Storage,
     dict: &Dictionary,
     uid: i64,
     word: &Word,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<Question, QuestionGenerationError> {
     // question kind: match the definition to the correct word
     let meaning: &WordMeaning = word
         .meanings
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
     let definition: &WordDefinition = meaning
         .definitions
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .ok_or(QuestionGenerationError::Unsupported)?;
 
     let answers_count = 4;
@@ -217,7 +351,8 @@ pub async fn generate_question_word_definition(
     answers.push(Answer {
         content: definition.definition.clone(),
         correct: true,
-        word_uid: None,
+        word_uid: Some(uid),
+        word: word.word.clone(),
     });
     invalid_words.push(&word.word[..]);
     // let antonym_answer = definition
@@ -226,11 +361,11 @@ pub async fn generate_question_word_definition(
     //     .or_else(|| meaning.antonyms.choose(&mut rand::thread_rng()));
     while let Some(anonym) = definition
         .antonyms
-        .choose(&mut rand::thread_rng())
-        .or_else(|| meaning.antonyms.choose(&mut rand::thread_rng()))
+        .choose(rng)
+        .or_else(|| meaning.antonyms.choose(&mut *rng))
     {
-        if rand::thread_rng().gen_bool(0.8) {
-            match dict.get_definition(&anonym).await {
+        if rng.gen_bool(0.8) {
+            match dict.get_definition(anonym).await {
                 Ok(word) => {
                     let antonym_meaning = word
                         .meanings
@@ -241,12 +376,17 @@ pub async fn generate_question_word_definition(
                             answers.push(Answer {
                                 content: antonym_meaning
                                     .definitions
-                                    .choose(&mut rand::thread_rng())
+                                    .choose(rng)
                                     .unwrap()
                                     .definition
                                     .clone(),
                                 correct: false,
-                                word_uid: None,
+                                word_uid: storage
+                                    .get_word(anonym)
+                                    .await
+                                    .map_err(QuestionGenerationError::Storage)?
+                                    .map(|word| word.uid),
+                                word: anonym.to_owned(),
                             });
                             invalid_words.push(anonym);
                             break;
@@ -261,84 +401,276 @@ pub async fn generate_question_word_definition(
     }
     invalid_words.extend(word.all_synonyms().chain(word.all_antonyms()));
 
+    let distractors = distractors.with_part_of_speech(meaning.part_of_speech.clone());
     let max_existing_words = answers_count - answers.len();
-    let existing_words_limit = rand::thread_rng().gen_range(1..=max_existing_words);
+    let existing_words_limit = rng.gen_range(1..=max_existing_words);
     add_definitions_from_storage(
         storage,
         dict,
         &mut answers,
         &invalid_words,
         existing_words_limit,
-        meaning.part_of_speech.clone(),
+        &distractors,
+        rng,
     )
     .await
     .map_err(QuestionGenerationError::Storage)?;
 
     let random_words_count = answers_count - answers.len();
-    add_random_definitions(
-        dict,
-        &mut answers,
-        &invalid_words,
-        random_words_count,
-        meaning.part_of_speech.clone(),
-    )
-    .await
-    .map_err(QuestionGenerationError::Dictionary)?;
+    add_random_definitions(dict, &mut answers, &invalid_words, random_words_count, &distractors, rng)
+        .await
+        .map_err(QuestionGenerationError::Dictionary)?;
 
     Ok(Question {
         word_uid: uid,
+        word: word.word.clone(),
+        kind: QuestionKind::WordToDefinition,
         question: format!("The definition of '{}' is:", word.word),
         answers,
+        hints: QuestionHints::new(word, meaning, definition),
+    })
+}
+
+/// Needs a translation backend, a word it knows translations of and enough other translations for the wrong answers
+pub async fn generate_question_word_translation(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    uid: i64,
+    word: &Word,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> Result<Question, QuestionGenerationError> {
+    let translations = dict.translations().ok_or(QuestionGenerationError::Unsupported)?;
+    let translation = word
+        .translations
+        .choose(rng)
+        .ok_or(QuestionGenerationError::Unsupported)?;
+
+    let answers_count = 4;
+    let mut answers = Vec::with_capacity(answers_count);
+    answers.push(Answer {
+        content: translation.clone(),
+        correct: true,
+        word_uid: Some(uid),
+        word: word.word.clone(),
+    });
+    // other words translated the same way would be right answers as well
+    let synonyms = translations.words_translated_as(translation);
+    let invalid_words = synonyms
+        .iter()
+        .map(String::as_str)
+        .chain(word.all_synonyms())
+        .chain(Some(&word.word[..]))
+        .collect::<Vec<&str>>();
+    let invalid_translations = |candidate: &str| {
+        word.translations.iter().any(|translation| translation == candidate)
+            || answers.iter().any(|answer: &Answer| answer.content == candidate)
+    };
+
+    let saved = storage
+        .find_words_excluding(&invalid_words, answers_count * CANDIDATE_POOL_FACTOR, &distractors.filter, rng)
+        .await
+        .map_err(QuestionGenerationError::Storage)?;
+    let mut candidates = saved
+        .into_iter()
+        .filter_map(|entry| {
+            let translation = translations
+                .translate(&entry.word)
+                .into_iter()
+                .find(|translation| !invalid_translations(translation))?;
+            Some(Answer {
+                content: translation,
+                correct: false,
+                word_uid: Some(entry.uid),
+                word: entry.word,
+            })
+        })
+        .collect::<Vec<Answer>>();
+    let missing = (answers_count - answers.len()).saturating_sub(candidates.len());
+    if missing > 0 {
+        // too few saved words have translations, any other known translations fill the gap
+        let known = translations
+            .all_translations()
+            .into_iter()
+            .filter(|candidate| {
+                !invalid_translations(candidate) && !candidates.iter().any(|answer| answer.content == *candidate)
+            })
+            .choose_multiple(rng, missing * CANDIDATE_POOL_FACTOR);
+        for candidate in known {
+            if let Some(source) = translations.words_translated_as(&candidate).into_iter().next() {
+                candidates.push(Answer {
+                    content: candidate,
+                    correct: false,
+                    word_uid: None,
+                    word: source,
+                });
+            }
+        }
+    }
+    let picked = distractors::pick(
+        &candidates,
+        |answer| &answer.content,
+        translation,
+        answers_count - answers.len(),
+        distractors.difficulty,
+        rng,
+    );
+    answers.extend(picked.into_iter().cloned());
+    // every known translation was a candidate, so there is nothing left to fill the missing answers with
+    if answers.len() < answers_count {
+        return Err(QuestionGenerationError::Unsupported);
+    }
+
+    Ok(Question {
+        word_uid: uid,
+        word: word.word.clone(),
+        kind: QuestionKind::WordToTranslation,
+        question: format!("How do you say '{}' in {}?", word.word, translations.target_language()),
+        answers,
+        hints: QuestionHints::of_word(word),
+    })
+}
+
+/// Needs a translation backend and a word it knows translations of
+pub async fn generate_question_translation_word(
+    storage: &impl Storage,
+    dict: &Dictionary,
+    uid: i64,
+    word: &Word,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> Result<Question, QuestionGenerationError> {
+    let translations = dict.translations().ok_or(QuestionGenerationError::Unsupported)?;
+    let translation = word
+        .translations
+        .choose(rng)
+        .ok_or(QuestionGenerationError::Unsupported)?;
+
+    let answers_count = 4;
+    let mut answers = Vec::with_capacity(answers_count);
+    answers.push(Answer {
+        content: word.word.clone(),
+        correct: true,
+        word_uid: Some(uid),
+        word: word.word.clone(),
+    });
+    // other words translated the same way would be right answers as well
+    let synonyms = translations.words_translated_as(translation);
+    let invalid_words = synonyms
+        .iter()
+        .map(String::as_str)
+        .chain(word.all_synonyms())
+        .chain(Some(&word.word[..]))
+        .collect::<Vec<&str>>();
+
+    let max_existing_words = answers_count - answers.len();
+    let existing_words_limit = rng.gen_range(1..=max_existing_words);
+    add_from_storage(storage, &mut answers, &invalid_words, existing_words_limit, distractors, rng)
+        .await
+        .map_err(QuestionGenerationError::Storage)?;
+
+    let random_words_count = answers_count - answers.len();
+    add_random(dict, &mut answers, &invalid_words, random_words_count, distractors, rng)
+        .await
+        .map_err(QuestionGenerationError::Dictionary)?;
+
+    Ok(Question {
+        word_uid: uid,
+        word: word.word.clone(),
+        kind: QuestionKind::TranslationToWord,
+        question: format!("Which {} word means '{translation}'?", dict.language()),
+        answers,
+        hints: QuestionHints::of_word(word),
     })
 }
 
+/// How many more candidates than needed are considered, so the most fitting ones can be picked
+const CANDIDATE_POOL_FACTOR: usize = 4;
+
+/// The word the distractors should be easy to confuse with, which is the word behind the right answer
+fn reference_word(answers: &[Answer]) -> String {
+    answers
+        .iter()
+        .find(|answer| answer.correct)
+        .map(|answer| answer.word.clone())
+        .unwrap_or_default()
+}
+
 async fn add_from_storage(
-    storage: &Storage,
+    storage: &impl Storage,
     answers: &mut Vec<Answer>,
     invalid_words: &Vec<&str>,
     count: usize,
-) -> sqlx::Result<()> {
-    let words = storage.find_words_excluding(&invalid_words, count).await?;
-    for word in words.iter() {
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> StorageResult<()> {
+    if count == 0 {
+        return Ok(());
+    }
+    let mut words = storage
+        .find_words_excluding(invalid_words, count * CANDIDATE_POOL_FACTOR, &distractors.filter, rng)
+        .await?;
+    words.retain(|word| distractors.allows_level(Level::of(&word.word)));
+    let reference = reference_word(answers);
+    let picked = distractors::pick(&words, |word| &word.word, &reference, count, distractors.difficulty, rng);
+    for word in picked {
         answers.push(Answer {
             content: word.word.clone(),
             correct: false,
             word_uid: Some(word.uid),
+            word: word.word.clone(),
         });
     }
     Ok(())
 }
 
+/// When the filter has a part of speech only definitions of that part of speech are used
 async fn add_definitions_from_storage(
-    storage: &Storage,
+    storage: &impl Storage,
     dict: &Dictionary,
     answers: &mut Vec<Answer>,
     invalid_words: &Vec<&str>,
     mut count: usize,
-    part_of_speech: PartOfSpeech,
-) -> sqlx::Result<()> {
-    let words = storage
-        .find_words_excluding(&invalid_words, count * 2)
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> StorageResult<()> {
+    if count == 0 {
+        return Ok(());
+    }
+    let filter = &distractors.filter;
+    let mut words = storage
+        .find_words_excluding(invalid_words, count * CANDIDATE_POOL_FACTOR, filter, rng)
         .await?;
-    for word in words.iter() {
-        if let Ok(definition) = dict.get_definition(&word.word).await {
-            if let Some(definition) = definition
-                .meanings
-                .into_iter()
-                .find(|meaning| meaning.part_of_speech == part_of_speech)
+    words.retain(|word| distractors.allows_level(Level::of(&word.word)));
+    // some words may lack a definition of the right part of speech, so a few spares are looked up too
+    let reference = reference_word(answers);
+    let words = distractors::pick(&words, |word| &word.word, &reference, count * 2, distractors.difficulty, rng);
+    // the lookups run concurrently, once enough definitions were found the rest are dropped
+    let mut definitions = pin!(stream::iter(words.into_iter())
+        .map(|word| async move { (word, dict.get_definition(&word.word).await) })
+        .buffered(DEFINITION_LOOKUP_CONCURRENCY));
+    while let Some((word, definition)) = definitions.next().await {
+        if let Ok(definition) = definition {
+            if let Some(definition) = definition.meanings.into_iter().find(|meaning| {
+                filter
+                    .part_of_speech
+                    .as_ref()
+                    .is_none_or(|part_of_speech| meaning.part_of_speech == *part_of_speech)
+            })
             {
                 answers.push(Answer {
                     content: definition
                         .definitions
-                        .choose(&mut rand::thread_rng())
+                        .choose(rng)
                         .unwrap()
                         .definition
                         .to_owned(),
                     correct: false,
-                    word_uid: None,
+                    word_uid: Some(word.uid),
+                    word: word.word.clone(),
                 });
                 count -= 1;
-                if count <= 0 {
+                if count == 0 {
                     break;
                 }
             }
@@ -347,65 +679,435 @@ async fn add_definitions_from_storage(
     Ok(())
 }
 
+/// Fetches random words that aren't invalid. For english words of a similar frequency are taken from the frequency
+/// list when possible, otherwise at higher difficulties the random words have the length of the reference
+async fn fetch_random_candidates(
+    dict: &Dictionary,
+    invalid_words: &[&str],
+    count: usize,
+    reference: &str,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
+) -> Result<Vec<String>, DictionaryError> {
+    let english = dict.language() == Language::English;
+    if english && is_phrase(reference) {
+        // phrases are told apart from other phrases, random single words would give the answer away
+        let phrases = phrases()
+            .into_iter()
+            .filter(|phrase| !invalid_words.contains(phrase) && !same_lemma(phrase, reference))
+            .collect::<Vec<&str>>();
+        if !phrases.is_empty() {
+            return Ok(phrases
+                .choose_multiple(rng, count)
+                .map(|phrase| (*phrase).to_owned())
+                .collect());
+        }
+    }
+    if let Some(levels) = distractors.random_word_levels(reference).filter(|_| english) {
+        let words = words_in_levels(levels)
+            .into_iter()
+            .filter(|word| !invalid_words.contains(word) && !word.eq_ignore_ascii_case(reference))
+            .collect::<Vec<&str>>();
+        if !words.is_empty() {
+            return Ok(words
+                .choose_multiple(rng, count)
+                .map(|word| (*word).to_owned())
+                .collect());
+        }
+    }
+    let query = RandomWordQuery::new(count)
+        .exclude(invalid_words.iter().chain([&reference]))
+        .seed(rng.gen());
+    let mut words = if distractors.difficulty.matches_length() {
+        match dict
+            .random_words(&query.clone().length(reference.chars().count()))
+            .await
+        {
+            // there may not be enough words of that length
+            Err(DictionaryError::NotEnoughWords { .. }) => dict.random_words(&query).await?,
+            words => words?,
+        }
+    } else {
+        dict.random_words(&query).await?
+    };
+    words.retain(|word| distractors.allows_level(Level::of(word)));
+    Ok(words)
+}
+
 async fn add_random(
     dict: &Dictionary,
     answers: &mut Vec<Answer>,
     invalid_words: &Vec<&str>,
     count: usize,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<(), DictionaryError> {
-    if count > 0 {
-        let words = dict
-            .get_random_words(count * 2, None)
-            .await?
-            .into_iter()
-            .filter(|word| !invalid_words.contains(&&word[..]))
-            .take(count)
-            .map(|word| Answer {
-                content: word,
-                correct: false,
-                word_uid: None,
-            });
-        answers.extend(words);
+    if count == 0 {
+        return Ok(());
+    }
+    let reference = reference_word(answers);
+    // distractors already taken from storage can't be picked again
+    let used = used_words(invalid_words, answers).collect::<Vec<&str>>();
+    let words = fetch_random_candidates(
+        dict,
+        &used,
+        count * CANDIDATE_POOL_FACTOR,
+        &reference,
+        distractors,
+        rng,
+    )
+    .await?;
+    let picked = distractors::pick(&words, |word| word, &reference, count, distractors.difficulty, rng);
+    let missing = count - picked.len();
+    answers.extend(picked.into_iter().map(|word| Answer {
+        content: word.clone(),
+        correct: false,
+        word_uid: None,
+        word: word.clone(),
+    }));
+    if missing > 0 {
+        // the level limit may have left too few candidates, any other random words fill the gap
+        let query = RandomWordQuery::new(missing)
+            .exclude(used_words(invalid_words, answers))
+            .seed(rng.gen());
+        answers.extend(dict.random_words(&query).await?.into_iter().map(|word| Answer {
+            content: word.clone(),
+            correct: false,
+            word_uid: None,
+            word,
+        }));
     }
     Ok(())
 }
 
+/// Words that can't be used as a new answer, the invalid ones and the ones already answers
+fn used_words<'a>(invalid_words: &'a [&str], answers: &'a [Answer]) -> impl Iterator<Item = &'a str> {
+    invalid_words
+        .iter()
+        .copied()
+        .chain(answers.iter().map(|answer| answer.word.as_str()))
+}
+
+/// When the filter has a part of speech only definitions of that part of speech are used
 async fn add_random_definitions(
     dict: &Dictionary,
     answers: &mut Vec<Answer>,
     invalid_words: &Vec<&str>,
     count: usize,
-    part_of_speech: PartOfSpeech,
+    distractors: &DistractorOptions,
+    rng: &mut impl Rng,
 ) -> Result<(), DictionaryError> {
-    let words = dict
-        .get_random_words(count * 3, None)
-        .await?
-        .into_iter()
-        .filter(|word| !invalid_words.contains(&&word[..]));
+    if count == 0 {
+        return Ok(());
+    }
+    let reference = reference_word(answers);
+    // distractors already taken from storage can't be picked again
+    let used = used_words(invalid_words, answers).collect::<Vec<&str>>();
+    let words = fetch_random_candidates(
+        dict,
+        &used,
+        count * CANDIDATE_POOL_FACTOR,
+        &reference,
+        distractors,
+        rng,
+    )
+    .await?;
+    let words = distractors::pick(&words, |word| word, &reference, count * 3, distractors.difficulty, rng);
+    let mut definitions = pin!(stream::iter(words.into_iter())
+        .map(|word| dict.get_definition(word))
+        .buffered(DEFINITION_LOOKUP_CONCURRENCY));
     let mut added = 0;
-    for word in words {
-        if let Ok(definition) = dict.get_definition(&word).await {
-            if let Some(definition) = definition
-                .meanings
-                .into_iter()
-                .find(|meaning| meaning.part_of_speech == part_of_speech)
-            {
-                answers.push(Answer {
-                    content: definition
-                        .definitions
-                        .choose(&mut rand::thread_rng())
-                        .unwrap()
-                        .definition
-                        .to_owned(),
-                    correct: false,
-                    word_uid: None,
-                });
-                if added >= count {
-                    break;
-                }
-                added += 1;
+    while let Some(definition) = definitions.next().await {
+        if let Some(answer) = definition.ok().and_then(|definition| definition_answer(definition, distractors, rng)) {
+            answers.push(answer);
+            added += 1;
+            if added >= count {
+                break;
             }
         }
     }
+    if added < count {
+        // many random words can't be defined, these top up the answers with ones that can
+        let mut query = RandomWordQuery::new(count - added)
+            .exclude(used_words(invalid_words, answers))
+            .seed(rng.gen());
+        if let Some(part_of_speech) = &distractors.filter.part_of_speech {
+            query = query.part_of_speech(part_of_speech.clone());
+        }
+        for definition in dict.random_definitions(&query).await? {
+            answers.extend(definition_answer(definition, distractors, rng));
+        }
+    }
     Ok(())
 }
+
+/// A wrong answer with one of the definitions of the word, None when it has none of the allowed part of speech
+fn definition_answer(definition: Word, distractors: &DistractorOptions, rng: &mut impl Rng) -> Option<Answer> {
+    let meaning = definition.meanings.into_iter().find(|meaning| {
+        distractors
+            .filter
+            .part_of_speech
+            .as_ref()
+            .is_none_or(|part_of_speech| meaning.part_of_speech == *part_of_speech)
+    })?;
+    Some(Answer {
+        content: meaning.definitions.choose(rng)?.definition.to_owned(),
+        correct: false,
+        word_uid: None,
+        word: definition.word,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc};
+
+    use dictionary::{HttpConfig, RandomWordBackend, TranslationFile};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    const TRANSLATIONS: &str = "
+        happy|feliz, contento
+        sad|triste
+        angry|enfadado
+        bright|brillante
+        quiet|tranquilo
+        tired|cansado
+    ";
+
+    /// Nothing is looked up online, the words come from the frequency list, the phrases and the translation file
+    fn offline_dictionary() -> Dictionary {
+        let translations = TranslationFile::parse(TRANSLATIONS, Language::English, Language::Spanish).unwrap();
+        Dictionary::builder()
+            .dictionary_url("http://127.0.0.1:9/")
+            .random_word_url("http://127.0.0.1:9")
+            .random_word_backend(RandomWordBackend::FrequencyList)
+            .translations(Arc::new(translations))
+            .config(HttpConfig {
+                max_retries: 0,
+                ..HttpConfig::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    async fn storage_with(words: &[&str]) -> SqliteStorage {
+        let storage = SqliteStorage::in_memory().await.unwrap();
+        for word in words {
+            storage.add_word(word).await.unwrap();
+        }
+        storage
+    }
+
+    fn happy() -> Word {
+        Word {
+            word: "happy".to_owned(),
+            language: Language::English,
+            phonetic: None,
+            phonetics: Vec::new(),
+            origin: None,
+            meanings: vec![WordMeaning {
+                part_of_speech: PartOfSpeech::Adjective,
+                definitions: vec![WordDefinition {
+                    definition: "Feeling pleasure or contentment.".to_owned(),
+                    example: Some("She was happy to help.".to_owned()),
+                    synonyms: vec!["glad".to_owned()],
+                    antonyms: vec!["sad".to_owned()],
+                }],
+                synonyms: vec!["cheerful".to_owned()],
+                antonyms: Vec::new(),
+            }],
+            translations: vec!["feliz".to_owned(), "contento".to_owned()],
+        }
+    }
+
+    fn answers(question: &Question) -> Vec<(&str, bool)> {
+        question
+            .answers
+            .iter()
+            .map(|answer| (answer.content.as_str(), answer.correct))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_synonym() {
+        let storage = storage_with(&["happy", "angry", "bright", "quiet"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let question = generate_question_word_synonym(
+            &storage,
+            &offline_dictionary(),
+            1,
+            &happy(),
+            true,
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(question.question, "What is the synonym of happy?");
+        assert_eq!(answers(&question), [("cheerful", true), ("sad", false), ("angry", false), ("quiet", false)]);
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_antonym() {
+        let storage = storage_with(&["happy", "angry", "bright", "quiet"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let question = generate_question_word_synonym(
+            &storage,
+            &offline_dictionary(),
+            1,
+            &happy(),
+            false,
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(question.question, "What is the antonym of happy?");
+        assert_eq!(answers(&question), [("cheerful", false), ("sad", true), ("angry", false), ("quiet", false)]);
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_word_of_a_definition() {
+        let storage = storage_with(&["happy", "angry", "bright", "quiet"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let question = generate_question_definition_word(
+            &storage,
+            &offline_dictionary(),
+            1,
+            &happy(),
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            question.question,
+            "What word matches the following definition? \"Feeling pleasure or contentment.\""
+        );
+        assert_eq!(answers(&question), [("happy", true), ("sad", false), ("bright", false), ("condition", false)]);
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_definition_of_a_word() {
+        // phrases are defined by the bundled list, so their definitions don't need the dictionary api
+        let storage = storage_with(&["red herring", "piece of cake", "the last straw", "wild goose chase"]).await;
+        let dict = offline_dictionary();
+        let red_herring = dict.get_definition("red herring").await.unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let question = generate_question_word_definition(
+            &storage,
+            &dict,
+            1,
+            &red_herring,
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(question.question, "The definition of 'red herring' is:");
+        assert_eq!(
+            answers(&question),
+            [
+                ("Something that misleads or distracts from the relevant question.", true),
+                ("A hopeless search for something that can't be found.", false),
+                ("Something that is very easy to do.", false),
+                ("The final problem in a series that makes a situation unbearable.", false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn saved_phrases_are_not_repeated_as_random_answers() {
+        // every random phrase is saved too, so the ones already taken from storage are candidates again
+        let storage = storage_with(&phrases()).await;
+        let dict = offline_dictionary();
+        let red_herring = dict.get_definition("red herring").await.unwrap();
+        for seed in 0..50 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let question = generate_question_definition_word(
+                &storage,
+                &dict,
+                1,
+                &red_herring,
+                &DistractorOptions::default(),
+                &mut rng,
+            )
+            .await
+            .unwrap();
+
+            let words = question.answers.iter().map(|answer| &answer.word).collect::<HashSet<_>>();
+            assert_eq!(words.len(), question.answers.len(), "seed {seed}: {:?}", answers(&question));
+        }
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_translation_of_a_word() {
+        let storage = storage_with(&["happy", "angry", "bright", "quiet"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let question = generate_question_word_translation(
+            &storage,
+            &offline_dictionary(),
+            1,
+            &happy(),
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(question.question, "How do you say 'happy' in Spanish?");
+        assert_eq!(answers(&question), [("feliz", true), ("brillante", false), ("enfadado", false), ("tranquilo", false)]);
+    }
+
+    #[tokio::test]
+    async fn needs_enough_translations_for_every_answer() {
+        // only two translations are left for the three wrong answers
+        let translations = "happy|feliz\nsad|triste\nangry|enfadado";
+        let translations = TranslationFile::parse(translations, Language::English, Language::Spanish).unwrap();
+        let dict = Dictionary::builder()
+            .random_word_backend(RandomWordBackend::FrequencyList)
+            .translations(Arc::new(translations))
+            .build()
+            .unwrap();
+        let storage = storage_with(&["happy", "sad", "angry"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let result = generate_question_word_translation(
+            &storage,
+            &dict,
+            1,
+            &happy(),
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await;
+
+        assert!(matches!(result, Err(QuestionGenerationError::Unsupported)));
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_word_of_a_translation() {
+        let storage = storage_with(&["happy", "angry", "bright", "quiet"]).await;
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let question = generate_question_translation_word(
+            &storage,
+            &offline_dictionary(),
+            1,
+            &happy(),
+            &DistractorOptions::default(),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(question.question, "Which English word means 'feliz'?");
+        assert_eq!(answers(&question), [("happy", true), ("angry", false), ("offer", false), ("best", false)]);
+    }
+}
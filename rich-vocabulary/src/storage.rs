@@ -1,5 +1,12 @@
+use std::{error::Error, fmt::Display, time::Duration};
+
 use chrono::NaiveDateTime;
-use sqlx::{migrate::MigrateDatabase, query, query_as, FromRow, Pool, Sqlite, SqlitePool};
+use dictionary::{normalize, Language, PartOfSpeech, Word};
+use rand::{seq::SliceRandom, Rng};
+use sqlx::{
+    migrate::MigrateDatabase, query, query_as, query_scalar, sqlite::SqlitePoolOptions, FromRow, Pool, Row, Sqlite,
+    SqlitePool,
+};
 
 const DB_URL: &str = "sqlite://sqlite.db";
 
@@ -9,49 +16,288 @@ pub struct WordEntry {
     pub word: String,
     pub last_quizzed: NaiveDateTime,
     pub score: i64,
+    /// Unknown for words saved before the creation time was recorded
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Narrows down which saved words can be picked, e.g. when looking for distractors
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    pub deck: Option<i64>,
+    /// Words whose parts of speech were never recorded still pass this filter,
+    /// they are only known once a word was looked up in the dictionary
+    pub part_of_speech: Option<PartOfSpeech>,
+    pub min_score: Option<i64>,
+    pub max_score: Option<i64>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct DeckEntry {
+    pub uid: i64,
+    pub name: String,
+}
+
+/// Which practice sessions to look at
+#[derive(Debug, Clone, Copy)]
+pub enum SessionRange {
+    /// The last sessions in which anything was answered
+    Last(u32),
+    /// Every answer given today, in local time
+    Today,
+}
+
+/// A word that was answered incorrectly, there is one entry for every kind of question it was missed in
+#[derive(Debug, FromRow)]
+pub struct MistakeEntry {
+    pub word_uid: i64,
+    pub word: String,
+    pub question_kind: String,
+}
+
+/// A pool of an empty in memory database without any migrations applied
+async fn in_memory_pool() -> sqlx::Result<SqlitePool> {
+    // every connection to an in memory database gets a database of its own,
+    // so the pool must keep exactly one connection alive for its whole lifetime
+    SqlitePoolOptions::new()
+        .min_connections(1)
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
 }
 
-pub struct Storage {
+/// Only sees the words and decks of one language at a time, the same pool can be shared by a storage for every language
+pub struct SqliteStorage {
     pool: Pool<Sqlite>,
+    language: Language,
 }
 
-impl Storage {
+impl SqliteStorage {
     pub async fn initialize() -> sqlx::Result<Self> {
         if !Sqlite::database_exists(DB_URL).await.unwrap_or(false) {
             Sqlite::create_database(DB_URL).await?;
         }
         let pool = SqlitePool::connect(DB_URL).await?;
         sqlx::migrate!().run(&pool).await?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            language: Language::default(),
+        })
+    }
+
+    /// Creates a storage that only lives in memory, useful for tests and hosts that don't want to touch the disk
+    pub async fn in_memory() -> sqlx::Result<Self> {
+        let pool = in_memory_pool().await?;
+        sqlx::migrate!().run(&pool).await?;
+        Ok(Self {
+            pool,
+            language: Language::default(),
+        })
+    }
+
+    /// A storage of the same database for the words of another language
+    pub fn with_language(&self, language: Language) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            language,
+        }
     }
 }
 
-impl Storage {
-    pub async fn add_word(&self, word: &str) -> sqlx::Result<()> {
-        query!("INSERT INTO words(word, score) VALUES(?, ?)", word, 500)
-            .execute(&self.pool)
-            .await
-            .map(|_| ())
+/// A failure of the place the words are kept in, whatever the storage behind it is
+#[derive(Debug)]
+pub struct StorageError(Box<dyn Error + Send + Sync>);
+
+impl StorageError {
+    pub fn new(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<sqlx::Error> for StorageError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl Error for StorageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// Everything the practice logic needs from a place that keeps the saved words,
+/// `SqliteStorage` is the implementation used by the application
+pub trait Storage {
+    /// Saves a word, returns false if the word is already saved.
+    /// Words are normalized, so case and surrounding whitespace never make two saved words different
+    async fn add_word(&self, word: &str) -> StorageResult<bool>;
+
+    /// Selects words to practice, when a deck is given only the words in that deck are considered.
+    /// The randomness only comes from the rng, so a seeded rng selects the same words again
+    async fn select_random_by_score(
+        &self,
+        count: u32,
+        deck: Option<i64>,
+        rng: &mut impl Rng,
+    ) -> StorageResult<Vec<WordEntry>>;
+
+    async fn mark_word_as_quizzed_by_uid(&self, uid: i64) -> StorageResult<()>;
+
+    /// Finds the words that most need practice, the ones with the highest score that weren't quizzed for the longest
+    async fn find_due_words(&self, count: u32, deck: Option<i64>) -> StorageResult<Vec<WordEntry>>;
+
+    /// Attempt to remove a word, returns true if the word was removed
+    async fn remove_word(&self, word: &str) -> StorageResult<bool>;
+
+    /// Picks random saved words that are not excluded and match the filter, the rng decides which ones
+    async fn find_words_excluding(
+        &self,
+        exclude: &[&str],
+        max: usize,
+        filter: &WordFilter,
+        rng: &mut impl Rng,
+    ) -> StorageResult<Vec<WordEntry>>;
+
+    /// Records the parts of speech a word can be used as, they are used to filter words later on
+    async fn set_parts_of_speech(&self, uid: i64, word: &Word) -> StorageResult<()>;
+
+    /// Adds a score to a word, returns if the word was modified or not
+    async fn add_score_to_optional(
+        &self,
+        word: &str,
+        additional_score: i32,
+    ) -> StorageResult<bool>;
+
+    /// Adds a score to a word, returns if the word was modified or not
+    async fn multiply_score_by_uid(&self, uid: i64, modifier: f64) -> StorageResult<bool>;
+
+    async fn get_word(&self, word: &str) -> StorageResult<Option<WordEntry>>;
+
+    async fn list_words(&self) -> StorageResult<Vec<WordEntry>>;
+
+    /// Records that a hint was used while practicing a word
+    async fn record_hint_usage(&self, word_uid: i64, hint: &str) -> StorageResult<()>;
+
+    /// Starts a new practice session, returns its uid
+    async fn start_session(&self) -> StorageResult<i64>;
+
+    async fn record_answer(
+        &self,
+        session_uid: i64,
+        word_uid: i64,
+        question_kind: &str,
+        correct: bool,
+        latency: Duration,
+    ) -> StorageResult<()>;
+
+    /// Finds the words answered incorrectly in the given sessions, oldest mistakes first
+    async fn find_mistakes(&self, range: SessionRange) -> StorageResult<Vec<MistakeEntry>>;
+
+    /// Creates a new deck of the storage's language, returns false if a deck with that name already exists
+    async fn create_deck(&self, name: &str) -> StorageResult<bool>;
+
+    async fn get_deck(&self, name: &str) -> StorageResult<Option<DeckEntry>>;
+
+    async fn list_decks(&self) -> StorageResult<Vec<DeckEntry>>;
+
+    /// Renames a deck, returns true if the deck was renamed
+    async fn rename_deck(&self, name: &str, new_name: &str) -> StorageResult<bool>;
+
+    /// Deletes a deck, the words in it stay saved. Returns true if the deck was deleted
+    async fn delete_deck(&self, name: &str) -> StorageResult<bool>;
+
+    /// Adds a word to a deck, returns false if the word was already in the deck
+    async fn add_word_to_deck(&self, deck_uid: i64, word_uid: i64) -> StorageResult<bool>;
+
+    /// Removes a word from a deck, returns false if the word wasn't in the deck
+    async fn remove_word_from_deck(&self, deck_uid: i64, word_uid: i64) -> StorageResult<bool>;
+
+    /// Moves a word from one deck to another, returns false if the word wasn't in the source deck
+    async fn move_word_between_decks(
+        &self,
+        from_deck_uid: i64,
+        to_deck_uid: i64,
+        word_uid: i64,
+    ) -> StorageResult<bool>;
+
+    async fn get_deck_words(&self, deck_uid: i64) -> StorageResult<Vec<WordEntry>>;
+}
+
+impl Storage for SqliteStorage {
+    async fn add_word(&self, word: &str) -> StorageResult<bool> {
+        let word = normalize(word);
+        let language = self.language.code();
+        let result = query!(
+            "INSERT INTO words(word, score, language) VALUES(?, ?, ?) ON CONFLICT DO NOTHING",
+            word,
+            500,
+            language
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn select_random_by_score(&self, count: u32) -> sqlx::Result<Vec<WordEntry>> {
+    async fn select_random_by_score(
+        &self,
+        count: u32,
+        deck: Option<i64>,
+        rng: &mut impl Rng,
+    ) -> StorageResult<Vec<WordEntry>> {
         let rows = query(
             "
-            SELECT *, (-(score * ((SELECT MAX(JULIANDAY(last_quizzed) * 24) as latest_quiz FROM words) - JULIANDAY(last_quizzed) * 24) / (SELECT MAX(last_quizzed) AS latest_quiz FROM words)) * (1.5 - RANDOM() / CAST(-9223372036854775808 AS REAL) / 2)) AS priority FROM words ORDER BY priority LIMIT ?;
+            SELECT *, (-(score * ((SELECT MAX(JULIANDAY(last_quizzed) * 24) as latest_quiz FROM words WHERE language = ?2) - JULIANDAY(last_quizzed) * 24) / (SELECT MAX(last_quizzed) AS latest_quiz FROM words WHERE language = ?2))) AS priority FROM words
+            WHERE (?1 IS NULL OR uid IN (SELECT word_uid FROM deck_words WHERE deck_uid = ?1)) AND language = ?2
+            ORDER BY uid;
             ",
         )
-        .bind(count)
+        .bind(deck)
+        .bind(self.language.code())
         .fetch_all(&self.pool).await?;
-        
+
         let mut entries = Vec::with_capacity(rows.len());
         for row in rows {
-            // println!("word: {:?}, score: {:?}, priority: {:?}, time: {:?}", row.try_get::<String, &str>("word"), row.try_get::<i64, &str>("score"), row.try_get::<f32, &str>("priority"), row.try_get::<NaiveDateTime, &str>("last_quizzed"));
-            entries.push(WordEntry::from_row(&row)?);
+            let priority = row.try_get::<Option<f64>, &str>("priority")?.unwrap_or_default();
+            // words of a similar priority take turns, the rng and not sqlite decides so a seed repeats the order
+            entries.push((priority * rng.gen_range(1.0..=2.0), WordEntry::from_row(&row)?));
         }
-        Ok(entries)
+        entries.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Ok(entries
+            .into_iter()
+            .take(count as usize)
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    async fn find_due_words(&self, count: u32, deck: Option<i64>) -> StorageResult<Vec<WordEntry>> {
+        let language = self.language.code();
+        query_as!(
+            WordEntry,
+            "
+            SELECT uid, word, last_quizzed, score, created_at FROM words
+            WHERE (?1 IS NULL OR uid IN (SELECT word_uid FROM deck_words WHERE deck_uid = ?1)) AND language = ?3
+            ORDER BY score * (JULIANDAY('now') - JULIANDAY(last_quizzed)) DESC LIMIT ?2
+            ",
+            deck,
+            count,
+            language
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(StorageError::from)
     }
 
-    pub async fn mark_word_as_quizzed_by_uid(&self, uid: i64) -> sqlx::Result<()> {
+    async fn mark_word_as_quizzed_by_uid(&self, uid: i64) -> StorageResult<()> {
         query!(
             "UPDATE words SET last_quizzed = CURRENT_TIMESTAMP WHERE uid = ?",
             uid
@@ -61,43 +307,93 @@ impl Storage {
         Ok(())
     }
 
-    /// Attempt to remove a word, returns true if the word was removed
-    pub async fn remove_word(&self, word: &str) -> Result<bool, sqlx::Error> {
-        let result = query!("DELETE FROM words WHERE word = ?", word).execute(&self.pool);
+    async fn remove_word(&self, word: &str) -> StorageResult<bool> {
+        let word = normalize(word);
+        let language = self.language.code();
+        let result = query!("DELETE FROM words WHERE word = ? AND language = ?", word, language).execute(&self.pool);
         let modified_count = result.await?;
         Ok(modified_count.rows_affected() > 0)
     }
 
-    pub async fn find_words_excluding(
+    async fn find_words_excluding(
         &self,
         exclude: &[&str],
         max: usize,
-    ) -> Result<Vec<WordEntry>, sqlx::Error> {
-        let query = format!(
-            "SELECT * FROM words WHERE word NOT IN (\"{}\") ORDER BY RANDOM() LIMIT {max};",
-            exclude.join("\",\"")
-        );
-        query_as(&query).fetch_all(&self.pool).await
+        filter: &WordFilter,
+        rng: &mut impl Rng,
+    ) -> StorageResult<Vec<WordEntry>> {
+        // the excluded words are passed as a json array so they never end up inside the sql
+        let exclude = serde_json::to_string(exclude).expect("a list of strings is always valid json");
+        let part_of_speech = filter.part_of_speech.as_ref().map(PartOfSpeech::as_str);
+        let language = self.language.code();
+        let mut words = query_as!(
+            WordEntry,
+            "
+            SELECT uid, word, last_quizzed, score, created_at FROM words
+            WHERE word NOT IN (SELECT value FROM json_each(?1))
+            AND (?2 IS NULL OR uid IN (SELECT word_uid FROM deck_words WHERE deck_uid = ?2))
+            AND (
+                ?3 IS NULL
+                OR uid IN (SELECT word_uid FROM word_parts_of_speech WHERE part_of_speech = ?3)
+                OR uid NOT IN (SELECT word_uid FROM word_parts_of_speech)
+            )
+            AND (?4 IS NULL OR score >= ?4)
+            AND (?5 IS NULL OR score <= ?5)
+            AND language = ?6
+            ORDER BY uid
+            ",
+            exclude,
+            filter.deck,
+            part_of_speech,
+            filter.min_score,
+            filter.max_score,
+            language
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        // shuffled by the rng rather than by sqlite, so a seeded practice picks the same words
+        words.shuffle(rng);
+        words.truncate(max);
+        Ok(words)
     }
 
-    /// Adds a score to a word, returns if the word was modified or not
-    pub async fn add_score_to_optional(
+    async fn set_parts_of_speech(&self, uid: i64, word: &Word) -> StorageResult<()> {
+        let mut transaction = self.pool.begin().await?;
+        query!("DELETE FROM word_parts_of_speech WHERE word_uid = ?", uid)
+            .execute(&mut *transaction)
+            .await?;
+        for meaning in &word.meanings {
+            let part_of_speech = meaning.part_of_speech.as_str();
+            query!(
+                "INSERT OR IGNORE INTO word_parts_of_speech(word_uid, part_of_speech) VALUES(?, ?)",
+                uid,
+                part_of_speech
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await.map_err(StorageError::from)
+    }
+
+    async fn add_score_to_optional(
         &self,
         word: &str,
         additional_score: i32,
-    ) -> Result<bool, sqlx::Error> {
+    ) -> StorageResult<bool> {
+        let word = normalize(word);
+        let language = self.language.code();
         let result = query!(
-            "UPDATE words SET score = score + ? WHERE word = ?",
+            "UPDATE words SET score = score + ? WHERE word = ? AND language = ?",
             additional_score,
-            word
+            word,
+            language
         )
         .execute(&self.pool);
         let modified_count = result.await?;
         Ok(modified_count.rows_affected() > 0)
     }
 
-    /// Adds a score to a word, returns if the word was modified or not
-    pub async fn multiply_score_by_uid(&self, uid: i64, modifier: f64) -> Result<bool, sqlx::Error> {
+    async fn multiply_score_by_uid(&self, uid: i64, modifier: f64) -> StorageResult<bool> {
         let round_weight = if modifier > 1.0 { 0.5 } else { -0.5 };
         let result = query!(
             "UPDATE words SET score = MIN(ROUND(score * ? + ?), 1000) WHERE uid = ?",
@@ -110,7 +406,721 @@ impl Storage {
         Ok(modified_count.rows_affected() > 0)
     }
 
-    pub async fn get_word(&self, word: &str) -> Result<Option<WordEntry>, sqlx::Error> {
-        query_as!(WordEntry, "SELECT * FROM words WHERE word = ?", word).fetch_optional(&self.pool).await
+    async fn get_word(&self, word: &str) -> StorageResult<Option<WordEntry>> {
+        let word = normalize(word);
+        let language = self.language.code();
+        query_as!(WordEntry, "SELECT uid, word, last_quizzed, score, created_at FROM words WHERE word = ? AND language = ?", word, language)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    async fn list_words(&self) -> StorageResult<Vec<WordEntry>> {
+        let language = self.language.code();
+        query_as!(WordEntry, "SELECT uid, word, last_quizzed, score, created_at FROM words WHERE language = ? ORDER BY word", language)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    async fn record_hint_usage(&self, word_uid: i64, hint: &str) -> StorageResult<()> {
+        query!(
+            "INSERT INTO hint_usages (word_uid, hint) VALUES (?, ?)",
+            word_uid,
+            hint
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn start_session(&self) -> StorageResult<i64> {
+        let result = query!("INSERT INTO sessions DEFAULT VALUES")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn record_answer(
+        &self,
+        session_uid: i64,
+        word_uid: i64,
+        question_kind: &str,
+        correct: bool,
+        latency: Duration,
+    ) -> StorageResult<()> {
+        let latency_ms = i64::try_from(latency.as_millis()).unwrap_or(i64::MAX);
+        query!(
+            "INSERT INTO answers (session_uid, word_uid, question_kind, correct, latency_ms) VALUES (?, ?, ?, ?, ?)",
+            session_uid,
+            word_uid,
+            question_kind,
+            correct,
+            latency_ms
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_mistakes(&self, range: SessionRange) -> StorageResult<Vec<MistakeEntry>> {
+        let language = self.language.code();
+        match range {
+            SessionRange::Last(count) => {
+                query_as!(
+                    MistakeEntry,
+                    r#"SELECT words.uid AS "word_uid!", words.word AS "word!", answers.question_kind AS "question_kind!"
+                    FROM answers
+                    JOIN words ON words.uid = answers.word_uid
+                    WHERE NOT answers.correct AND words.language = ?1 AND answers.session_uid IN (
+                        SELECT DISTINCT session_answers.session_uid FROM answers AS session_answers
+                        JOIN words AS session_words ON session_words.uid = session_answers.word_uid
+                        WHERE session_words.language = ?1
+                        ORDER BY session_answers.session_uid DESC LIMIT ?2
+                    )
+                    GROUP BY words.uid, answers.question_kind
+                    ORDER BY MIN(answers.uid)"#,
+                    language,
+                    count
+                )
+                .fetch_all(&self.pool)
+                .await
+                .map_err(StorageError::from)
+            }
+            SessionRange::Today => {
+                query_as!(
+                    MistakeEntry,
+                    r#"SELECT words.uid AS "word_uid!", words.word AS "word!", answers.question_kind AS "question_kind!"
+                    FROM answers
+                    JOIN words ON words.uid = answers.word_uid
+                    WHERE NOT answers.correct AND words.language = ?
+                    AND date(answers.answered_at, 'localtime') = date('now', 'localtime')
+                    GROUP BY words.uid, answers.question_kind
+                    ORDER BY MIN(answers.uid)"#,
+                    language
+                )
+                .fetch_all(&self.pool)
+                .await
+                .map_err(StorageError::from)
+            }
+        }
+    }
+
+    async fn create_deck(&self, name: &str) -> StorageResult<bool> {
+        let language = self.language.code();
+        let result = query!("INSERT OR IGNORE INTO decks(name, language) VALUES(?, ?)", name, language)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_deck(&self, name: &str) -> StorageResult<Option<DeckEntry>> {
+        let language = self.language.code();
+        query_as!(DeckEntry, "SELECT uid, name FROM decks WHERE name = ? AND language = ?", name, language)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    async fn list_decks(&self) -> StorageResult<Vec<DeckEntry>> {
+        let language = self.language.code();
+        query_as!(DeckEntry, "SELECT uid, name FROM decks WHERE language = ? ORDER BY name", language)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    async fn rename_deck(&self, name: &str, new_name: &str) -> StorageResult<bool> {
+        let language = self.language.code();
+        let result = query!("UPDATE decks SET name = ? WHERE name = ? AND language = ?", new_name, name, language)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_deck(&self, name: &str) -> StorageResult<bool> {
+        let language = self.language.code();
+        let result = query!("DELETE FROM decks WHERE name = ? AND language = ?", name, language)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_word_to_deck(&self, deck_uid: i64, word_uid: i64) -> StorageResult<bool> {
+        let result = query!(
+            "INSERT OR IGNORE INTO deck_words(deck_uid, word_uid) VALUES(?, ?)",
+            deck_uid,
+            word_uid
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_word_from_deck(&self, deck_uid: i64, word_uid: i64) -> StorageResult<bool> {
+        let result = query!(
+            "DELETE FROM deck_words WHERE deck_uid = ? AND word_uid = ?",
+            deck_uid,
+            word_uid
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn move_word_between_decks(
+        &self,
+        from_deck_uid: i64,
+        to_deck_uid: i64,
+        word_uid: i64,
+    ) -> StorageResult<bool> {
+        let mut transaction = self.pool.begin().await?;
+        let removed = query!(
+            "DELETE FROM deck_words WHERE deck_uid = ? AND word_uid = ?",
+            from_deck_uid,
+            word_uid
+        )
+        .execute(&mut *transaction)
+        .await?;
+        if removed.rows_affected() == 0 {
+            return Ok(false);
+        }
+        query!(
+            "INSERT OR IGNORE INTO deck_words(deck_uid, word_uid) VALUES(?, ?)",
+            to_deck_uid,
+            word_uid
+        )
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    async fn get_deck_words(&self, deck_uid: i64) -> StorageResult<Vec<WordEntry>> {
+        let language = self.language.code();
+        query_as!(
+            WordEntry,
+            "SELECT words.uid, words.word, words.last_quizzed, words.score, words.created_at FROM words JOIN deck_words ON deck_words.word_uid = words.uid WHERE deck_words.deck_uid = ? AND words.language = ? ORDER BY words.word",
+            deck_uid,
+            language
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(StorageError::from)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct IntegrityReport {
+    /// Problems reported by SQLite's own integrity check, these can't be repaired
+    pub corruption: Vec<String>,
+    pub dangling_deck_words: i64,
+    pub empty_words: i64,
+    /// Words that only differ by case or surrounding whitespace from an older entry of the same language
+    pub duplicate_words: i64,
+    pub untrimmed_words: i64,
+    pub scores_out_of_range: i64,
+    pub future_timestamps: i64,
+}
+
+impl IntegrityReport {
+    pub fn is_healthy(&self) -> bool {
+        self.problems().is_empty()
+    }
+
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = self.corruption.clone();
+        let counts = [
+            (self.dangling_deck_words, "deck entries pointing at missing words or decks"),
+            (self.empty_words, "empty words"),
+            (self.duplicate_words, "duplicate words"),
+            (self.untrimmed_words, "words with surrounding whitespace"),
+            (self.scores_out_of_range, "scores outside of 0-1000"),
+            (self.future_timestamps, "words practiced in the future"),
+        ];
+        for (count, description) in counts {
+            if count > 0 {
+                problems.push(format!("{count} {description}"));
+            }
+        }
+        problems
+    }
+}
+
+impl SqliteStorage {
+    pub async fn check_integrity(&self) -> sqlx::Result<IntegrityReport> {
+        let corruption: Vec<String> = query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .filter(|message: &String| message != "ok")
+            .collect();
+        let dangling_deck_words = query_scalar!(
+            "SELECT COUNT(*) FROM deck_words WHERE word_uid NOT IN (SELECT uid FROM words) OR deck_uid NOT IN (SELECT uid FROM decks)"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        let empty_words = query_scalar!("SELECT COUNT(*) FROM words WHERE TRIM(word) = ''")
+            .fetch_one(&self.pool)
+            .await?;
+        let duplicate_words = query_scalar!(
+            "SELECT COUNT(*) - COUNT(DISTINCT language || ':' || LOWER(TRIM(word))) FROM words WHERE TRIM(word) != ''"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        let untrimmed_words = query_scalar!("SELECT COUNT(*) FROM words WHERE word != TRIM(word)")
+            .fetch_one(&self.pool)
+            .await?;
+        let scores_out_of_range =
+            query_scalar!("SELECT COUNT(*) FROM words WHERE score < 0 OR score > 1000")
+                .fetch_one(&self.pool)
+                .await?;
+        let future_timestamps = query_scalar!(
+            "SELECT COUNT(*) FROM words WHERE last_quizzed > CURRENT_TIMESTAMP OR created_at > CURRENT_TIMESTAMP"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(IntegrityReport {
+            corruption,
+            dangling_deck_words: dangling_deck_words.into(),
+            empty_words: empty_words.into(),
+            duplicate_words: duplicate_words.into(),
+            untrimmed_words: untrimmed_words.into(),
+            scores_out_of_range: scores_out_of_range.into(),
+            future_timestamps: future_timestamps.into(),
+        })
+    }
+
+    /// Fixes every problem `check_integrity` can report except for corruption.
+    /// Duplicates are merged into their oldest entry with the LOWER(TRIM(word)) key of the migration that merged
+    /// them first, only within a language, after their scores and timestamps were brought back into range.
+    pub async fn repair(&self) -> sqlx::Result<()> {
+        let mut transaction = self.pool.begin().await?;
+        query!(
+            "DELETE FROM deck_words WHERE word_uid NOT IN (SELECT uid FROM words) OR deck_uid NOT IN (SELECT uid FROM decks)"
+        )
+        .execute(&mut *transaction)
+        .await?;
+        query!("DELETE FROM words WHERE TRIM(word) = ''")
+            .execute(&mut *transaction)
+            .await?;
+        query!("UPDATE words SET score = MAX(MIN(score, 1000), 0) WHERE score < 0 OR score > 1000")
+            .execute(&mut *transaction)
+            .await?;
+        query!("UPDATE words SET last_quizzed = CURRENT_TIMESTAMP WHERE last_quizzed > CURRENT_TIMESTAMP")
+            .execute(&mut *transaction)
+            .await?;
+        query!("UPDATE words SET created_at = CURRENT_TIMESTAMP WHERE created_at > CURRENT_TIMESTAMP")
+            .execute(&mut *transaction)
+            .await?;
+        query(
+            "
+            CREATE TEMPORARY TABLE word_merges AS
+                SELECT duplicate.uid AS duplicate_uid, kept.uid AS kept_uid
+                FROM words AS duplicate
+                JOIN (
+                    SELECT MIN(uid) AS uid, language, LOWER(TRIM(word)) AS key FROM words GROUP BY language, LOWER(TRIM(word))
+                ) AS kept
+                    ON duplicate.language = kept.language AND LOWER(TRIM(duplicate.word)) = kept.key AND duplicate.uid != kept.uid;
+            UPDATE words SET
+                score = (
+                    SELECT ROUND(AVG(group_words.score)) FROM words AS group_words
+                    WHERE group_words.language = words.language AND LOWER(TRIM(group_words.word)) = LOWER(TRIM(words.word))
+                ),
+                last_quizzed = (
+                    SELECT MAX(group_words.last_quizzed) FROM words AS group_words
+                    WHERE group_words.language = words.language AND LOWER(TRIM(group_words.word)) = LOWER(TRIM(words.word))
+                ),
+                created_at = (
+                    SELECT MIN(group_words.created_at) FROM words AS group_words
+                    WHERE group_words.language = words.language AND LOWER(TRIM(group_words.word)) = LOWER(TRIM(words.word))
+                )
+            WHERE uid IN (SELECT kept_uid FROM word_merges);
+            INSERT OR IGNORE INTO deck_words(deck_uid, word_uid)
+                SELECT deck_words.deck_uid, word_merges.kept_uid FROM deck_words
+                JOIN word_merges ON deck_words.word_uid = word_merges.duplicate_uid;
+            DELETE FROM words WHERE uid IN (SELECT duplicate_uid FROM word_merges);
+            DROP TABLE word_merges;
+            ",
+        )
+        .execute(&mut *transaction)
+        .await?;
+        query!("UPDATE words SET word = TRIM(word) WHERE word != TRIM(word)")
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    async fn storage() -> SqliteStorage {
+        SqliteStorage::in_memory().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn saves_normalized_words_once() {
+        let storage = storage().await;
+        assert!(storage.add_word("  Ephemeral ").await.unwrap());
+        assert!(!storage.add_word("EPHEMERAL").await.unwrap());
+
+        let saved = storage.list_words().await.unwrap();
+        assert_eq!(saved.iter().map(|entry| entry.word.as_str()).collect::<Vec<&str>>(), ["ephemeral"]);
+        assert_eq!(storage.get_word("ephemeral ").await.unwrap().unwrap().score, 500);
+        assert!(storage.remove_word("Ephemeral").await.unwrap());
+        assert!(storage.get_word("ephemeral").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn scores_change_within_bounds() {
+        let storage = storage().await;
+        storage.add_word("ephemeral").await.unwrap();
+        let uid = storage.get_word("ephemeral").await.unwrap().unwrap().uid;
+
+        assert!(storage.add_score_to_optional("ephemeral", 100).await.unwrap());
+        assert!(!storage.add_score_to_optional("zymurgy", 100).await.unwrap());
+        assert!(storage.multiply_score_by_uid(uid, 0.5).await.unwrap());
+        assert_eq!(storage.get_word("ephemeral").await.unwrap().unwrap().score, 300);
+        storage.multiply_score_by_uid(uid, 10.0).await.unwrap();
+        assert_eq!(storage.get_word("ephemeral").await.unwrap().unwrap().score, 1000);
+    }
+
+    #[tokio::test]
+    async fn finds_the_mistakes_of_the_last_sessions() {
+        let storage = storage().await;
+        for word in ["ephemeral", "fleeting", "transient"] {
+            storage.add_word(word).await.unwrap();
+        }
+        let uid = |word: &'static str| {
+            let storage = &storage;
+            async move { storage.get_word(word).await.unwrap().unwrap().uid }
+        };
+        let first = storage.start_session().await.unwrap();
+        storage
+            .record_answer(first, uid("transient").await, "synonym", false, Duration::ZERO)
+            .await
+            .unwrap();
+        let second = storage.start_session().await.unwrap();
+        for (word, kind, correct) in [
+            ("ephemeral", "synonym", false),
+            ("ephemeral", "antonym", false),
+            ("ephemeral", "synonym", false),
+            ("fleeting", "synonym", true),
+        ] {
+            storage
+                .record_answer(second, uid(word).await, kind, correct, Duration::from_millis(800))
+                .await
+                .unwrap();
+        }
+
+        let mistakes = storage.find_mistakes(SessionRange::Last(1)).await.unwrap();
+        let mistakes = mistakes
+            .iter()
+            .map(|mistake| (mistake.word.as_str(), mistake.question_kind.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        assert_eq!(mistakes, [("ephemeral", "synonym"), ("ephemeral", "antonym")]);
+        assert_eq!(storage.find_mistakes(SessionRange::Last(2)).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn the_last_sessions_are_counted_per_language() {
+        let english = storage().await;
+        let spanish = english.with_language(Language::Spanish);
+        english.add_word("ephemeral").await.unwrap();
+        spanish.add_word("efímero").await.unwrap();
+        let ephemeral = english.get_word("ephemeral").await.unwrap().unwrap();
+        let efimero = spanish.get_word("efímero").await.unwrap().unwrap();
+        let session = english.start_session().await.unwrap();
+        english
+            .record_answer(session, ephemeral.uid, "synonym", false, Duration::ZERO)
+            .await
+            .unwrap();
+        // a later spanish session isn't one of the last english sessions
+        let session = spanish.start_session().await.unwrap();
+        spanish
+            .record_answer(session, efimero.uid, "synonym", false, Duration::ZERO)
+            .await
+            .unwrap();
+
+        let mistakes = english.find_mistakes(SessionRange::Last(1)).await.unwrap();
+        assert_eq!(mistakes.len(), 1);
+        assert_eq!(mistakes[0].word, "ephemeral");
+        assert_eq!(spanish.find_mistakes(SessionRange::Last(1)).await.unwrap()[0].word, "efímero");
+    }
+
+    #[tokio::test]
+    async fn languages_only_see_their_own_words() {
+        let english = storage().await;
+        let spanish = english.with_language(Language::Spanish);
+        english.add_word("ephemeral").await.unwrap();
+        assert!(spanish.add_word("efímero").await.unwrap());
+        // the same spelling is a different word in another language
+        assert!(spanish.add_word("ephemeral").await.unwrap());
+
+        assert_eq!(english.list_words().await.unwrap().len(), 1);
+        assert_eq!(spanish.list_words().await.unwrap().len(), 2);
+        assert!(english.get_word("efímero").await.unwrap().is_none());
+        assert!(spanish.remove_word("ephemeral").await.unwrap());
+        assert!(english.get_word("ephemeral").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn languages_only_see_their_own_decks() {
+        let english = storage().await;
+        let spanish = english.with_language(Language::Spanish);
+        assert!(english.create_deck("verbs").await.unwrap());
+        // the same name is a different deck in another language
+        assert!(spanish.create_deck("verbs").await.unwrap());
+        assert!(spanish.create_deck("adjetivos").await.unwrap());
+        spanish.add_word("correr").await.unwrap();
+        let correr = spanish.get_word("correr").await.unwrap().unwrap();
+        let verbos = spanish.get_deck("verbs").await.unwrap().unwrap();
+        spanish.add_word_to_deck(verbos.uid, correr.uid).await.unwrap();
+
+        assert_eq!(english.list_decks().await.unwrap().len(), 1);
+        assert_eq!(spanish.list_decks().await.unwrap().len(), 2);
+        assert!(english.get_deck("adjetivos").await.unwrap().is_none());
+        assert!(!english.delete_deck("adjetivos").await.unwrap());
+        assert!(english.get_deck("verbs").await.unwrap().unwrap().uid != verbos.uid);
+        assert_eq!(spanish.get_deck_words(verbos.uid).await.unwrap()[0].word, "correr");
+    }
+
+    #[tokio::test]
+    async fn refuses_to_undo_languages_while_a_spelling_is_saved_in_several() {
+        let english = storage().await;
+        let german = english.with_language(Language::German);
+        english.add_word("gift").await.unwrap();
+        german.add_word("gift").await.unwrap();
+
+        assert!(sqlx::migrate!().undo(&english.pool, 20240419120000).await.is_err());
+        assert!(english.get_word("gift").await.unwrap().is_some());
+        assert!(german.get_word("gift").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn manages_decks_with_names_of_several_words() {
+        let storage = storage().await;
+        assert!(storage.create_deck("phrasal verbs").await.unwrap());
+        assert!(!storage.create_deck("phrasal verbs").await.unwrap());
+        assert!(storage.create_deck("idioms").await.unwrap());
+        assert!(storage.rename_deck("phrasal verbs", "verbs with particles").await.unwrap());
+        assert!(!storage.rename_deck("phrasal verbs", "verbs").await.unwrap());
+
+        let names = storage.list_decks().await.unwrap().into_iter().map(|deck| deck.name).collect::<Vec<String>>();
+        assert_eq!(names, ["idioms", "verbs with particles"]);
+        assert!(storage.get_deck("phrasal verbs").await.unwrap().is_none());
+        assert!(storage.delete_deck("idioms").await.unwrap());
+        assert!(!storage.delete_deck("idioms").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn moves_words_between_decks() {
+        let storage = storage().await;
+        storage.add_word("take after").await.unwrap();
+        storage.add_word("give up").await.unwrap();
+        storage.create_deck("phrasal verbs").await.unwrap();
+        storage.create_deck("learned").await.unwrap();
+        let verbs = storage.get_deck("phrasal verbs").await.unwrap().unwrap().uid;
+        let learned = storage.get_deck("learned").await.unwrap().unwrap().uid;
+        let take_after = storage.get_word("take after").await.unwrap().unwrap().uid;
+        let give_up = storage.get_word("give up").await.unwrap().unwrap().uid;
+
+        assert!(storage.add_word_to_deck(verbs, take_after).await.unwrap());
+        assert!(storage.add_word_to_deck(verbs, give_up).await.unwrap());
+        assert!(!storage.add_word_to_deck(verbs, give_up).await.unwrap());
+        assert!(storage.move_word_between_decks(verbs, learned, give_up).await.unwrap());
+        assert!(!storage.move_word_between_decks(verbs, learned, give_up).await.unwrap());
+        assert!(storage.remove_word_from_deck(verbs, take_after).await.unwrap());
+        assert!(!storage.remove_word_from_deck(verbs, take_after).await.unwrap());
+
+        assert!(storage.get_deck_words(verbs).await.unwrap().is_empty());
+        let learned_words = storage.get_deck_words(learned).await.unwrap();
+        assert_eq!(learned_words.iter().map(|entry| entry.word.as_str()).collect::<Vec<&str>>(), ["give up"]);
+        let filter = WordFilter {
+            deck: Some(learned),
+            ..Default::default()
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        assert_eq!(storage.find_words_excluding(&[], 10, &filter, &mut rng).await.unwrap().len(), 1);
+        assert_eq!(storage.select_random_by_score(10, Some(learned), &mut rng).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn repairs_what_the_integrity_check_finds() {
+        let storage = storage().await;
+        query(
+            "
+            INSERT INTO words(word, score, language, created_at) VALUES
+                ('ephemeral', 400, 'en', '2024-03-01 10:00:00'),
+                (' Ephemeral', 600, 'en', '2024-02-01 10:00:00'),
+                ('  ', 500, 'en', '2024-03-01 10:00:00'),
+                ('fleeting', 1500, 'en', '2024-03-01 10:00:00'),
+                ('ephemeral', 500, 'es', '2024-03-01 10:00:00');
+            UPDATE words SET last_quizzed = '2999-01-01 00:00:00' WHERE word = 'fleeting';
+            INSERT INTO decks(name) VALUES('adjectives');
+            INSERT INTO deck_words(deck_uid, word_uid) SELECT decks.uid, words.uid FROM decks, words WHERE words.word = ' Ephemeral';
+            ",
+        )
+        .execute(&storage.pool)
+        .await
+        .unwrap();
+
+        let report = storage.check_integrity().await.unwrap();
+        assert_eq!(report.duplicate_words, 1);
+        assert_eq!(report.empty_words, 1);
+        assert_eq!(report.untrimmed_words, 2);
+        assert_eq!(report.scores_out_of_range, 1);
+        assert_eq!(report.future_timestamps, 1);
+        storage.repair().await.unwrap();
+
+        assert!(storage.check_integrity().await.unwrap().is_healthy());
+        let words = storage.list_words().await.unwrap();
+        assert_eq!(words.iter().map(|entry| (entry.word.as_str(), entry.score)).collect::<Vec<(&str, i64)>>(), [
+            ("ephemeral", 500),
+            ("fleeting", 1000)
+        ]);
+        assert_eq!(words[0].created_at.unwrap().to_string(), "2024-02-01 10:00:00");
+        let deck = storage.get_deck("adjectives").await.unwrap().unwrap();
+        assert_eq!(storage.get_deck_words(deck.uid).await.unwrap()[0].uid, words[0].uid);
+        assert!(storage.with_language(Language::Spanish).get_word("ephemeral").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn migrations_leave_no_duplicates_for_the_integrity_check() {
+        let pool = in_memory_pool().await.unwrap();
+        for migration in sqlx::migrate!().iter() {
+            if migration.migration_type.is_down_migration() {
+                continue;
+            }
+            if migration.version == 20240315120000 {
+                // words saved before they were normalized
+                query("INSERT INTO words(word, score) VALUES('Ephemeral', 400), (' ephemeral ', 600), ('fleeting', 500)")
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+            query(&migration.sql).execute(&pool).await.unwrap();
+        }
+        let storage = SqliteStorage {
+            pool,
+            language: Language::English,
+        };
+
+        assert!(storage.check_integrity().await.unwrap().is_healthy());
+        let words = storage.list_words().await.unwrap();
+        assert_eq!(words.iter().map(|entry| entry.word.as_str()).collect::<Vec<&str>>(), ["Ephemeral", "fleeting"]);
+        assert_eq!(words[0].score, 500);
+        assert_eq!(words[0].created_at, None);
+    }
+
+    #[tokio::test]
+    async fn migrations_give_decks_the_language_of_their_words() {
+        let pool = in_memory_pool().await.unwrap();
+        for migration in sqlx::migrate!().iter() {
+            if migration.migration_type.is_down_migration() {
+                continue;
+            }
+            if migration.version == 20240426120100 {
+                // decks made before they had a language
+                query(
+                    "
+                    INSERT INTO words(word, score, language) VALUES('correr', 500, 'es'), ('saltar', 500, 'es'), ('run', 500, 'en');
+                    INSERT INTO decks(name) VALUES('verbos'), ('empty');
+                    INSERT INTO deck_words(deck_uid, word_uid) SELECT decks.uid, words.uid FROM decks, words WHERE decks.name = 'verbos';
+                    ",
+                )
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+            query(&migration.sql).execute(&pool).await.unwrap();
+        }
+        let english = SqliteStorage {
+            pool,
+            language: Language::English,
+        };
+        let spanish = english.with_language(Language::Spanish);
+
+        let verbos = spanish.get_deck("verbos").await.unwrap().unwrap();
+        assert_eq!(spanish.get_deck_words(verbos.uid).await.unwrap().len(), 2);
+        assert!(english.get_deck("verbos").await.unwrap().is_none());
+        assert!(english.get_deck("empty").await.unwrap().is_some());
+        // the english word stays in the deck, it's only hidden like before
+        assert!(english.check_integrity().await.unwrap().is_healthy());
+    }
+
+    #[tokio::test]
+    async fn the_same_seed_picks_the_same_words() {
+        let storage = storage().await;
+        for word in ["ephemeral", "fleeting", "transient", "brief", "momentary", "passing", "short", "quick"] {
+            storage.add_word(word).await.unwrap();
+        }
+        let picks = |seed| {
+            let storage = &storage;
+            async move {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let practiced = storage.select_random_by_score(3, None, &mut rng).await.unwrap();
+                let distractors = storage
+                    .find_words_excluding(&["ephemeral"], 3, &WordFilter::default(), &mut rng)
+                    .await
+                    .unwrap();
+                practiced
+                    .into_iter()
+                    .chain(distractors)
+                    .map(|entry| entry.word)
+                    .collect::<Vec<String>>()
+            }
+        };
+
+        assert_eq!(picks(1).await, picks(1).await);
+        assert_ne!(picks(1).await, picks(2).await);
+    }
+
+    /// Saves the words and returns the ones `find_words_excluding` picks when the others are excluded
+    async fn words_left_after_excluding(words: &[&str], exclude: &[&str]) -> Vec<String> {
+        let storage = storage().await;
+        for word in words {
+            assert!(storage.add_word(word).await.unwrap(), "{word}");
+        }
+        let mut left = storage
+            .find_words_excluding(exclude, words.len(), &WordFilter::default(), &mut ChaCha8Rng::seed_from_u64(7))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.word)
+            .collect::<Vec<String>>();
+        left.sort();
+        left
+    }
+
+    #[tokio::test]
+    async fn excludes_words_with_sql_and_json_metacharacters() {
+        let cases: [(&[&str], &[&str], &[&str]); 6] = [
+            // saved words, excluded words, words expected to be left
+            (&["it's", "o'clock", "x' or '1'='1"], &["it's", "x' or '1'='1"], &["o'clock"]),
+            (&["say \"hi\"", "\"),(", "a\"),(\"b"], &["\"),("], &["a\"),(\"b", "say \"hi\""]),
+            (&["100%", "snake_case", "snakexcase", "100"], &["100%", "snake_case"], &["100", "snakexcase"]),
+            (&["back\\slash", "back\\\\slash", "tab\\t"], &["back\\slash", "tab\\t"], &["back\\\\slash"]),
+            (&["{\"a\":[1]}", "[\"x\"]", "null", "]"], &["[\"x\"]", "null"], &["]", "{\"a\":[1]}"]),
+            (&["ñandú", "日本語", "façade", "facade"], &["façade", "日本語"], &["facade", "ñandú"]),
+        ];
+        for (words, exclude, expected) in cases {
+            assert_eq!(words_left_after_excluding(words, exclude).await, expected, "excluding {exclude:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn excludes_nothing_without_excluded_words() {
+        let words = ["ephemeral", "fleeting", "'", "%"];
+        let mut expected = words.map(str::to_owned).to_vec();
+        expected.sort();
+        assert_eq!(words_left_after_excluding(&words, &[]).await, expected);
+        // excluding words that aren't saved doesn't matter either
+        assert_eq!(words_left_after_excluding(&words, &["zymurgy", "%%", "_"]).await, expected);
     }
 }
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use dictionary::{frequency_rank, normalize, words_in_levels, Language, Level};
+
+/// How many suggestions are offered at most
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// How many typos a word of the given length may have to still be suggested
+fn max_distance(length: usize) -> usize {
+    match length {
+        0..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    }
+}
+
+/// Known words close to a misspelled one, the closest first and among equally close ones the most common first.
+/// Words come from the saved words, and for english from the frequency list too.
+pub fn suggestions<'a>(misspelled: &str, language: Language, saved: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let misspelled = normalize(misspelled);
+    let max_distance = max_distance(misspelled.chars().count());
+    let listed = match language {
+        Language::English => words_in_levels(Level::A1..=Level::C1),
+        _ => Vec::new(),
+    };
+    let known = listed
+        .into_iter()
+        .map(str::to_owned)
+        .chain(saved.into_iter().map(normalize))
+        .collect::<HashSet<String>>();
+    let mut close = known
+        .into_iter()
+        .filter(|word| *word != misspelled)
+        .map(|word| (strsim::damerau_levenshtein(&word, &misspelled), word))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<(usize, String)>>();
+    // saved words that aren't in the frequency list come after the listed ones
+    close.sort_by_key(|(distance, word)| {
+        let rank = frequency_rank(word).filter(|_| language == Language::English);
+        (*distance, rank.unwrap_or(u32::MAX), word.clone())
+    });
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, word)| word)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_closest_and_most_common_word_comes_first() {
+        assert_eq!(suggestions("goverment", Language::English, []).first().map(String::as_str), Some("government"));
+        // every word one typo away is as close, the most common one wins
+        assert_eq!(suggestions("teh", Language::English, [])[0], "the");
+        assert!(suggestions("teh", Language::English, []).len() <= MAX_SUGGESTIONS);
+    }
+
+    #[test]
+    fn words_too_far_away_are_not_suggested() {
+        assert!(suggestions("zxqvbnmk", Language::English, []).is_empty());
+        // a word of nine letters may have up to three typos
+        let close = suggestions("goverment", Language::English, []);
+        assert!(close.iter().all(|word| strsim::damerau_levenshtein(word, "goverment") <= 3), "{close:?}");
+        assert!(suggestions("tehx", Language::English, ["the"]).is_empty());
+    }
+
+    #[test]
+    fn saved_words_are_suggested_too() {
+        assert_eq!(suggestions("zymurgi", Language::English, ["zymurgy"]), ["zymurgy"]);
+        // only the saved words are known for other languages
+        assert_eq!(suggestions("contneto", Language::Spanish, ["contento", "triste"]), ["contento"]);
+        assert!(suggestions("goverment", Language::Spanish, []).is_empty());
+    }
+}
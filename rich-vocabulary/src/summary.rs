@@ -0,0 +1,232 @@
+use std::{fmt::Write as _, fs, path::Path, time::Duration};
+
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+
+use crate::storage::{Storage, StorageResult};
+
+/// Words with a score below this are considered mastered
+const MASTERED_SCORE: i64 = 200;
+/// How many of the words due next are listed
+const DUE_WORDS_COUNT: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordOutcome {
+    Correct,
+    Incorrect,
+    Skipped,
+}
+
+impl WordOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WordOutcome::Correct => "correct",
+            WordOutcome::Incorrect => "incorrect",
+            WordOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordResult {
+    pub word: String,
+    pub outcome: WordOutcome,
+    pub score_before: i64,
+    pub score_after: i64,
+    pub seconds: f64,
+}
+
+/// Keeps track of how every word went while a session is running
+#[derive(Debug)]
+pub struct SessionRecorder {
+    started_at: NaiveDateTime,
+    results: Vec<(String, i64, WordOutcome, Duration)>,
+}
+
+impl SessionRecorder {
+    pub fn start() -> Self {
+        Self {
+            started_at: Local::now().naive_local(),
+            results: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, word: &str, score_before: i64, outcome: WordOutcome, time: Duration) {
+        self.results
+            .push((word.to_owned(), score_before, outcome, time));
+    }
+
+    /// Looks up the scores after the session and the words that should be practiced next
+    pub async fn finish(self, storage: &impl Storage, deck: Option<i64>) -> StorageResult<SessionSummary> {
+        let duration = (Local::now().naive_local() - self.started_at)
+            .to_std()
+            .unwrap_or_default();
+        let mut words = Vec::with_capacity(self.results.len());
+        for (word, score_before, outcome, time) in self.results {
+            let score_after = storage
+                .get_word(&word)
+                .await?
+                .map_or(score_before, |entry| entry.score);
+            words.push(WordResult {
+                word,
+                outcome,
+                score_before,
+                score_after,
+                seconds: time.as_secs_f64(),
+            });
+        }
+        let newly_mastered = words
+            .iter()
+            .filter(|result| result.score_before >= MASTERED_SCORE && result.score_after < MASTERED_SCORE)
+            .map(|result| result.word.clone())
+            .collect();
+        let due_next = storage
+            .find_due_words(DUE_WORDS_COUNT, deck)
+            .await?
+            .into_iter()
+            .map(|entry| entry.word)
+            .collect();
+        Ok(SessionSummary {
+            started_at: self.started_at,
+            seconds: duration.as_secs_f64(),
+            words,
+            newly_mastered,
+            due_next,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSummary {
+    pub started_at: NaiveDateTime,
+    pub seconds: f64,
+    pub words: Vec<WordResult>,
+    pub newly_mastered: Vec<String>,
+    pub due_next: Vec<String>,
+}
+
+impl SessionSummary {
+    fn count(&self, outcome: WordOutcome) -> usize {
+        self.words
+            .iter()
+            .filter(|result| result.outcome == outcome)
+            .count()
+    }
+
+    pub fn print(&self) {
+        println!("========================================");
+        println!(
+            "Session summary: {} correct, {} incorrect, {} skipped in {}",
+            self.count(WordOutcome::Correct),
+            self.count(WordOutcome::Incorrect),
+            self.count(WordOutcome::Skipped),
+            format_seconds(self.seconds)
+        );
+        for result in &self.words {
+            println!(
+                "    {:<20} {:<9} score {} -> {} ({})",
+                result.word,
+                result.outcome.as_str(),
+                result.score_before,
+                result.score_after,
+                format_seconds(result.seconds)
+            );
+        }
+        if !self.newly_mastered.is_empty() {
+            println!("Newly mastered: {}", self.newly_mastered.join(", "));
+        }
+        if !self.due_next.is_empty() {
+            println!("Due next: {}", self.due_next.join(", "));
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        // writing to a string can't fail
+        let _ = writeln!(markdown, "# Practice session {}", self.started_at.format("%Y-%m-%d %H:%M"));
+        let _ = writeln!(markdown);
+        let _ = writeln!(
+            markdown,
+            "{} correct, {} incorrect, {} skipped in {}.",
+            self.count(WordOutcome::Correct),
+            self.count(WordOutcome::Incorrect),
+            self.count(WordOutcome::Skipped),
+            format_seconds(self.seconds)
+        );
+        let _ = writeln!(markdown);
+        let _ = writeln!(markdown, "| Word | Result | Score before | Score after | Time |");
+        let _ = writeln!(markdown, "| --- | --- | --- | --- | --- |");
+        for result in &self.words {
+            let _ = writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {} |",
+                result.word,
+                result.outcome.as_str(),
+                result.score_before,
+                result.score_after,
+                format_seconds(result.seconds)
+            );
+        }
+        if !self.newly_mastered.is_empty() {
+            let _ = writeln!(markdown);
+            let _ = writeln!(markdown, "Newly mastered: {}", self.newly_mastered.join(", "));
+        }
+        if !self.due_next.is_empty() {
+            let _ = writeln!(markdown);
+            let _ = writeln!(markdown, "Due next: {}", self.due_next.join(", "));
+        }
+        markdown
+    }
+
+    /// Writes the summary as json when the file ends with `.json`, otherwise as markdown
+    pub fn write_to(&self, path: &Path) -> anyhow::Result<()> {
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        let content = if is_json {
+            serde_json::to_string_pretty(self)?
+        } else {
+            self.to_markdown()
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+fn format_seconds(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{seconds:.1}s")
+    } else {
+        let seconds = seconds.round() as u64;
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    #[tokio::test]
+    async fn reports_score_changes_and_newly_mastered_words() {
+        let storage = SqliteStorage::in_memory().await.unwrap();
+        for word in ["ephemeral", "fleeting"] {
+            storage.add_word(word).await.unwrap();
+        }
+        storage.add_score_to_optional("ephemeral", -250).await.unwrap();
+        let mut recorder = SessionRecorder::start();
+        recorder.record("ephemeral", 250, WordOutcome::Correct, Duration::from_secs(2));
+        recorder.record("fleeting", 500, WordOutcome::Incorrect, Duration::from_secs(5));
+        let ephemeral = storage.get_word("ephemeral").await.unwrap().unwrap();
+        storage.multiply_score_by_uid(ephemeral.uid, 0.7).await.unwrap();
+
+        let summary = recorder.finish(&storage, None).await.unwrap();
+
+        assert_eq!(summary.words[0].score_after, 175);
+        assert_eq!(summary.words[1].score_after, 500);
+        assert_eq!(summary.newly_mastered, ["ephemeral"]);
+        assert_eq!(summary.count(WordOutcome::Incorrect), 1);
+        assert_eq!(summary.due_next.len(), 2);
+    }
+}
@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_QUESTION_TIME: Duration = Duration::from_secs(15);
+pub const DEFAULT_SESSION_TIME: Duration = Duration::from_secs(120);
+
+/// Time limits of a timed practice session
+#[derive(Debug, Clone, Copy)]
+pub struct TimeLimits {
+    pub per_question: Duration,
+    pub session: Duration,
+}
+
+impl Default for TimeLimits {
+    fn default() -> Self {
+        Self {
+            per_question: DEFAULT_QUESTION_TIME,
+            session: DEFAULT_SESSION_TIME,
+        }
+    }
+}
+
+/// The countdown of a single question
+#[derive(Debug, Clone, Copy)]
+pub struct Countdown {
+    /// The full time a question gets, used to rate how fast an answer was
+    limit: Duration,
+    /// When the question runs out of time, which can be earlier than the limit when the session ends first
+    deadline: Instant,
+}
+
+impl Countdown {
+    pub fn start(limit: Duration, session_deadline: Instant) -> Self {
+        Self {
+            limit,
+            deadline: (Instant::now() + limit).min(session_deadline),
+        }
+    }
+
+    /// None once the time is up
+    pub fn remaining(&self) -> Option<Duration> {
+        Some(self.deadline.saturating_duration_since(Instant::now())).filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Scales the score modifier of a correct answer by how fast it was given,
+    /// an instant answer improves the score half again as much as an untimed one and an answer at the limit half as much
+    pub fn speed_modifier(&self, modifier: f64, latency: Duration) -> f64 {
+        let speed = 1.0 - (latency.as_secs_f64() / self.limit.as_secs_f64()).clamp(0.0, 1.0);
+        1.0 - (1.0 - modifier) * (0.5 + speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_answers_improve_the_score_more() {
+        let countdown = Countdown::start(Duration::from_secs(10), Instant::now() + DEFAULT_SESSION_TIME);
+        let cases = [
+            (Duration::ZERO, 0.7),
+            (Duration::from_secs(5), 0.8),
+            (Duration::from_secs(10), 0.9),
+            // past the limit counts as an answer at the limit
+            (Duration::from_secs(25), 0.9),
+        ];
+        for (latency, expected) in cases {
+            let modifier = countdown.speed_modifier(0.8, latency);
+            assert!((modifier - expected).abs() < 1e-9, "latency {latency:?} gave {modifier}");
+        }
+    }
+}
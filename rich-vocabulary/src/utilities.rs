@@ -1,13 +1,97 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
 
+/// Lines read from stdin by a dedicated thread, so a read can be given up on without losing the line
+static LINES: OnceLock<Mutex<Receiver<io::Result<String>>>> = OnceLock::new();
 
+fn lines() -> &'static Mutex<Receiver<io::Result<String>>> {
+    LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let result = io::stdin().read_line(&mut line).map(|_| line);
+            // at the end of the input the thread stops, readers then get empty lines
+            let done = !matches!(&result, Ok(line) if !line.is_empty());
+            if sender.send(result).is_err() || done {
+                break;
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+fn receive(timeout: Option<Duration>) -> io::Result<Option<String>> {
+    let receiver = lines().lock().map_err(|_| io::Error::other("stdin reader was poisoned"))?;
+    let line = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match line {
+        Ok(line) => line.map(Some),
+        Err(RecvTimeoutError::Timeout) => Ok(None),
+        Err(RecvTimeoutError::Disconnected) => Ok(Some(String::new())),
+    }
+}
 
 pub fn input(prompt: &str) -> io::Result<String> {
-    let mut line = String::new();
     print!("{prompt}");
     io::stdout().flush()?;
-    io::stdin().read_line(&mut line)?;
-    Ok(line)
+    Ok(receive(None)?.unwrap_or_default())
+}
+
+/// Same as `input` but the blocking read happens on another thread,
+/// so other futures can make progress while waiting for the user
+pub async fn input_async(prompt: &str) -> io::Result<String> {
+    let prompt = prompt.to_owned();
+    tokio::task::spawn_blocking(move || input(&prompt))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Waits at most `timeout` for a line, returns None when the time ran out.
+/// Lines typed before the prompt was shown are thrown away, they were meant for an earlier prompt.
+pub async fn input_timeout(prompt: &str, timeout: Duration) -> io::Result<Option<String>> {
+    let prompt = prompt.to_owned();
+    tokio::task::spawn_blocking(move || {
+        if let Ok(receiver) = lines().lock() {
+            while receiver.try_recv().is_ok() {}
+        }
+        print!("{prompt}");
+        io::stdout().flush()?;
+        receive(Some(timeout))
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// Splits a command line into arguments at whitespace, text in double quotes stays one argument,
+/// e.g. `rename "phrasal verbs" verbs` has the arguments `rename`, `phrasal verbs` and `verbs`
+pub fn split_arguments(line: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    arguments.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+    arguments
 }
 
 pub fn str_to_bool(mut str: String) -> Option<bool> {
@@ -17,4 +101,20 @@ pub fn str_to_bool(mut str: String) -> Option<bool> {
         "n" | "no" | "nope" | "false" | "off" => Some(false),
         _ => None,
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_arguments_stay_together() {
+        assert_eq!(
+            split_arguments(r#" rename "phrasal verbs"  verbs "#),
+            ["rename", "phrasal verbs", "verbs"]
+        );
+        assert_eq!(split_arguments("create phrasal verbs"), ["create", "phrasal", "verbs"]);
+        assert_eq!(split_arguments(r#"add "red herring"#), ["add", "red herring"]);
+        assert!(split_arguments("   ").is_empty());
+    }
+}